@@ -1,22 +1,16 @@
-use ratatui::crossterm::event::{self, KeyCode, KeyEventKind};
+use ratatui::crossterm::event::{Event, KeyCode, KeyEventKind};
 use ratatui::text::Text;
 use ratatui::widgets::Block;
 use ratatui::{layout::*, Frame};
+use std::time::Duration;
+use topogi_renderer::layout::center_rect;
 use topogi_renderer::Result;
 use topogi_renderer::UIEngine;
 
-fn _center(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
-    let [area] = Layout::horizontal([horizontal])
-        .flex(Flex::Center)
-        .areas(area);
-    let [area] = Layout::vertical([vertical]).flex(Flex::Center).areas(area);
-    area
-}
-
 fn _render(frame: &mut Frame) {
     let text = Text::raw("Hello world!");
     let block = Block::bordered();
-    let area = _center(
+    let area = center_rect(
         frame.size(),
         Constraint::Length(text.width() as u16 + 2),
         Constraint::Length(3),
@@ -27,22 +21,30 @@ fn _render(frame: &mut Frame) {
 
 fn main() -> Result<()> {
     let mut ui = UIEngine::new().unwrap();
-    let source = r#"
-    (layer
-        (block "Json Editor" "content" (style (border all))))
-    "#;
-    let mut parser = topogi_lang::parser::Parser::new(source);
-    let exp = parser.parse_exp().unwrap();
+    let mut status = String::from("content");
 
     loop {
+        let source = format!(
+            r#"
+            (layer
+                (block "Json Editor" "{}" (style (border all))))
+            "#,
+            status
+        );
+        let mut parser = topogi_lang::parser::Parser::new(&source);
+        let exp = parser.parse_exp().unwrap();
         ui.render(&exp).unwrap();
 
-        if event::poll(std::time::Duration::from_millis(16))? {
-            if let event::Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press && key.code == KeyCode::Char('q') {
-                    break;
-                }
+        match ui.poll_event(Duration::from_millis(16))? {
+            Some(Event::Key(key))
+                if key.kind == KeyEventKind::Press && key.code == KeyCode::Char('q') =>
+            {
+                break;
+            }
+            Some(Event::Mouse(mouse)) => {
+                status = format!("mouse at {}, {}", mouse.column, mouse.row);
             }
+            _ => {}
         }
     }
 
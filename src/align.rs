@@ -0,0 +1,141 @@
+use ratatui::layout::{Alignment, Rect};
+use topogi_lang::ast::Exp;
+
+use crate::{
+    render_tree::{
+        check_symbol, create_alignment, create_list_with_minlen, RenderTree, RenderTreeError,
+        Result,
+    },
+    renderer::measure_height,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum VerticalAlignment {
+    Top,
+    Center,
+    Bottom,
+}
+
+fn create_vertical_alignment(exp: &Exp) -> Result<VerticalAlignment> {
+    match exp.as_symbol() {
+        Some("top") => Ok(VerticalAlignment::Top),
+        Some("center") => Ok(VerticalAlignment::Center),
+        Some("bottom") => Ok(VerticalAlignment::Bottom),
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "top | center | bottom",
+            exp.into(),
+        )),
+    }
+}
+
+/// `(content-align h v)`, or `(content-align x)` with `x` naming a single
+/// axis, or `center` for both.
+pub fn content_align(exp: &Exp) -> Result<(Alignment, VerticalAlignment)> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "content-align")?;
+
+    match elems[1..] {
+        [ref one] => match one.as_symbol() {
+            Some("center") => Ok((Alignment::Center, VerticalAlignment::Center)),
+            Some("top" | "bottom") => Ok((Alignment::Left, create_vertical_alignment(one)?)),
+            _ => Ok((create_alignment(one)?, VerticalAlignment::Top)),
+        },
+        [ref horizontal, ref vertical] => Ok((
+            create_alignment(horizontal)?,
+            create_vertical_alignment(vertical)?,
+        )),
+        _ => Err(RenderTreeError::InvalidLength(exp.into())),
+    }
+}
+
+/// The natural width of text content, or `None` for content that stretches
+/// to fill whatever width it is given.
+fn content_width(content: &RenderTree) -> Option<u16> {
+    match content {
+        RenderTree::Text(text) => Some(text.width() as u16),
+        RenderTree::Paragraph {
+            text, wrap: false, ..
+        } => text.lines().map(|line| line.chars().count() as u16).max(),
+        _ => None,
+    }
+}
+
+/// The part of `area` that `content` occupies once aligned. Content without
+/// a natural size keeps the full width or height.
+pub fn aligned_area(
+    content: &RenderTree,
+    area: Rect,
+    horizontal: Alignment,
+    vertical: VerticalAlignment,
+) -> Rect {
+    let width = content_width(content).map_or(area.width, |width| width.min(area.width));
+    let height = match measure_height(content, width) {
+        0 => area.height,
+        height => height.min(area.height),
+    };
+
+    let x = match horizontal {
+        Alignment::Left => area.x,
+        Alignment::Center => area.x + (area.width - width) / 2,
+        Alignment::Right => area.right() - width,
+    };
+    let y = match vertical {
+        VerticalAlignment::Top => area.y,
+        VerticalAlignment::Center => area.y + (area.height - height) / 2,
+        VerticalAlignment::Bottom => area.bottom() - height,
+    };
+    Rect::new(x, y, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+    use ratatui::{buffer::Buffer, text::Text};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_content_align() {
+        assert_eq!(
+            content_align(&parse("(content-align center)")),
+            Ok((Alignment::Center, VerticalAlignment::Center))
+        );
+        assert_eq!(
+            content_align(&parse("(content-align bottom)")),
+            Ok((Alignment::Left, VerticalAlignment::Bottom))
+        );
+        assert_eq!(
+            content_align(&parse("(content-align right center)")),
+            Ok((Alignment::Right, VerticalAlignment::Center))
+        );
+        assert!(content_align(&parse("(content-align middle)")).is_err());
+    }
+
+    #[test]
+    fn test_aligned_area() {
+        let content = RenderTree::Text(Text::raw("ab"));
+        let area = Rect::new(1, 1, 6, 3);
+        assert_eq!(
+            aligned_area(&content, area, Alignment::Center, VerticalAlignment::Center),
+            Rect::new(3, 2, 2, 1)
+        );
+        assert_eq!(
+            aligned_area(&content, area, Alignment::Right, VerticalAlignment::Bottom),
+            Rect::new(5, 3, 2, 1)
+        );
+    }
+
+    #[test]
+    fn test_render_centered_block_content() {
+        let exp = parse(r#"(layer (block "ab" (style (border all) (content-align center))))"#);
+        assert_eq!(
+            UIEngine::render_to_buffer(&exp, 6, 5).unwrap(),
+            Buffer::with_lines(["┌────┐", "│    │", "│ ab │", "│    │", "└────┘"])
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::RenderTree;
+
+/// How many renders a software blink stays shown, then hidden.
+pub const BLINK_FRAMES: u64 = 30;
+
+/// `(blink)`, the text clause that makes the engine toggle the text.
+pub fn is_blink(exp: &Exp) -> bool {
+    exp.as_list()
+        .is_some_and(|elems| elems.len() == 1 && elems[0].as_symbol() == Some("blink"))
+}
+
+/// Shows or hides every blinking node in `tree` for the engine's `tick`th
+/// render.
+pub fn apply_blink(tree: &mut RenderTree, tick: u64) {
+    let shown = (tick / BLINK_FRAMES) % 2 == 0;
+    tree.walk_mut(&mut |node| {
+        if let RenderTree::Blink { visible, .. } = node {
+            *visible = shown;
+        }
+    });
+}
@@ -1,37 +1,311 @@
-use crate::render_tree::{
-    check_symbol, create_list_with_len, create_list_with_minlen, create_render_tree, RenderTree,
-    RenderTreeError, Result,
+use crate::{
+    align::{content_align, VerticalAlignment},
+    render_tree::{
+        check_symbol, create_alignment, create_integer, create_list_with_len,
+        create_list_with_minlen, create_render_tree_ctx, RenderContext, RenderTree,
+        RenderTreeError, Result,
+    },
+    style::{parse_color, style_clause, Palette},
+    text::{create_span, is_span},
 };
 use ratatui::{
-    layout::Alignment,
-    widgets::{Block, Borders},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{
+        block::{Position, Title},
+        Block, BorderType, Borders, Padding,
+    },
 };
 use topogi_lang::ast::Exp;
 
-pub fn create_block(exp: &Exp) -> Result<RenderTree> {
-    let elems = create_list_with_minlen(exp, 3)?;
+/// Border style of the block whose `(id ...)` has focus.
+pub const FOCUSED_BORDER_STYLE: Style = Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+
+/// The settings of a `(block ...)` node. Unlike ratatui's `Block`, whose
+/// fields are private, these can be compared field by field and serialized;
+/// `widget` turns them into the `Block` that is drawn.
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockStyle<'a> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::titles"))]
+    pub titles: Vec<Title<'a>>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::AlignmentDef"))]
+    pub title_alignment: Alignment,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::PositionDef"))]
+    pub title_position: Position,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::borders"))]
+    pub borders: Borders,
+    pub border_style: Style,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::BorderTypeDef"))]
+    pub border_type: BorderType,
+    pub style: Style,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::PaddingDef"))]
+    pub padding: Padding,
+    /// Set by `(border-when-focused ...)`: `borders` becomes these while the
+    /// block has focus and none otherwise.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::option_borders"))]
+    pub focus_borders: Option<Borders>,
+}
+
+impl<'a> BlockStyle<'a> {
+    pub fn new() -> Self {
+        BlockStyle::default()
+    }
+
+    pub fn title<T: Into<Title<'a>>>(mut self, title: T) -> Self {
+        self.titles.push(title.into());
+        self
+    }
+
+    pub fn title_alignment(mut self, alignment: Alignment) -> Self {
+        self.title_alignment = alignment;
+        self
+    }
+
+    pub fn title_position(mut self, position: Position) -> Self {
+        self.title_position = position;
+        self
+    }
+
+    pub fn borders(mut self, borders: Borders) -> Self {
+        self.borders = borders;
+        self
+    }
+
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = style;
+        self
+    }
+
+    pub fn border_type(mut self, border_type: BorderType) -> Self {
+        self.border_type = border_type;
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn focus_borders(mut self, borders: Borders) -> Self {
+        self.focus_borders = Some(borders);
+        self
+    }
+
+    pub fn widget(&self) -> Block<'a> {
+        self.titles.iter().cloned().fold(
+            Block::new()
+                .title_alignment(self.title_alignment)
+                .title_position(self.title_position)
+                .borders(self.borders)
+                .border_style(self.border_style)
+                .border_type(self.border_type)
+                .style(self.style)
+                .padding(self.padding),
+            |block, title| block.title(title),
+        )
+    }
+
+    /// The area left for the content once borders and padding are drawn.
+    pub fn inner(&self, area: Rect) -> Rect {
+        self.widget().inner(area)
+    }
+}
+
+fn is_clause(exp: &Exp) -> bool {
+    matches!(
+        exp.as_list()
+            .and_then(|elems| elems.first())
+            .and_then(|e| e.as_symbol()),
+        Some("style" | "id")
+    )
+}
+
+fn create_id(exp: &Exp) -> Result<String> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "id")?;
+
+    Ok(elems[1].to_string())
+}
+
+/// `(block [title] content [(style ...)] [(id name)])`. An omitted or empty
+/// title leaves the border unbroken; a `(span ...)` or a list of spans gives a
+/// styled one. Titles from a `(titles ...)` style clause are drawn after this
+/// one. A block with an id is drawn with `FOCUSED_BORDER_STYLE` while the
+/// engine's focus is on it.
+pub fn create_block(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
     check_symbol(&elems[0], "block")?;
 
-    let mut block = Block::new().title(elems[1].to_string());
-    let inner = create_render_tree(&elems[2])?;
-    if let Some(style) = elems.get(3) {
-        block = block_style(block.clone(), style)?;
+    let (title, mut rest) = if elems.len() == 2 || is_clause(&elems[2]) {
+        (None, &elems[1..])
+    } else {
+        (Some(&elems[1]), &elems[2..])
+    };
+    let mut id = None;
+    if let Some(Ok(name)) = rest.last().filter(|_| rest.len() > 1).map(create_id) {
+        id = Some(name);
+        rest = &rest[..rest.len() - 1];
+    }
+    if rest.len() > 2 {
+        return Err(RenderTreeError::InvalidLength(exp.into()));
     }
 
-    Ok(RenderTree::Block(block, Box::new(inner)))
+    let mut block = BlockStyle::new();
+    if let Some(title) = title {
+        if let Some(title) = create_title(title, ctx.palette)? {
+            block = block.title(title);
+        }
+    }
+    if let Some(style) = rest.get(1) {
+        block = block_style(block.clone(), style, ctx.palette)?;
+    }
+    if block.focus_borders.is_some() {
+        block.borders = Borders::NONE;
+    }
+    let inner_ctx = RenderContext {
+        area: block.inner(ctx.area),
+        ..*ctx
+    };
+    let mut inner = create_render_tree_ctx(&rest[0], &inner_ctx).map_err(|e| e.within("block"))?;
+    if let Some((horizontal, vertical)) = rest.get(1).and_then(block_content_align) {
+        inner = RenderTree::Aligned {
+            horizontal,
+            vertical,
+            content: Box::new(inner),
+        };
+    }
+
+    let block = RenderTree::Block(block, Box::new(inner));
+    Ok(match id {
+        Some(id) => RenderTree::Focusable {
+            id,
+            focused: false,
+            content: Box::new(block),
+        },
+        None => block,
+    })
 }
 
-pub fn block_style<'a>(mut block: Block<'a>, exp: &Exp) -> Result<Block<'a>> {
+/// Marks the node whose id is `focus` as focused, and every other one as not.
+pub fn apply_focus(tree: &mut RenderTree, focus: Option<&str>) {
+    match tree {
+        RenderTree::Focusable {
+            id,
+            focused,
+            content,
+        } => {
+            *focused = focus == Some(id.as_str());
+            if let RenderTree::Block(block, _) = content.as_mut() {
+                if let Some(borders) = block.focus_borders {
+                    block.borders = if *focused { borders } else { Borders::NONE };
+                }
+            }
+            apply_focus(content, focus);
+        }
+        RenderTree::Block(_, content)
+        | RenderTree::Float { content, .. }
+        | RenderTree::Aligned { content, .. }
+        | RenderTree::Blink { content, .. } => apply_focus(content, focus),
+        RenderTree::Stack(_, _, stack_elems) => stack_elems
+            .iter_mut()
+            .for_each(|e| apply_focus(&mut e.content, focus)),
+        RenderTree::Responsive(_, content, otherwise) => {
+            apply_focus(content, focus);
+            if let Some(otherwise) = otherwise {
+                apply_focus(otherwise, focus);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `(title "text" (fg c) ...)`, a title drawn in one style, e.g. a status
+/// icon and its label.
+fn create_styled_title(exp: &Exp, palette: Option<&Palette>) -> Result<Line<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "title")?;
+
+    let mut style = Style::default();
+    for clause in &elems[2..] {
+        style = style_clause(style, clause, palette)?;
+    }
+    Ok(Line::from(Span::styled(elems[1].to_string(), style)))
+}
+
+fn create_title(exp: &Exp, palette: Option<&Palette>) -> Result<Option<Line<'static>>> {
+    if exp
+        .as_list()
+        .and_then(|elems| elems.first())
+        .and_then(|e| e.as_symbol())
+        == Some("title")
+    {
+        return create_styled_title(exp, palette).map(Some);
+    }
+    if is_span(exp) {
+        return Ok(Some(Line::from(create_span(exp, palette)?)));
+    }
+    if let Some(elems) = exp
+        .as_list()
+        .filter(|elems| !elems.is_empty() && elems.iter().all(is_span))
+    {
+        let spans = elems
+            .iter()
+            .map(|e| create_span(e, palette))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(Some(Line::from(spans)));
+    }
+
+    let title = exp.to_string();
+    Ok((!title.is_empty()).then(|| Line::from(title)))
+}
+
+fn block_content_align(style: &Exp) -> Option<(Alignment, VerticalAlignment)> {
+    style.as_list()?.iter().find_map(|e| content_align(e).ok())
+}
+
+pub fn block_style<'a>(
+    mut block: BlockStyle<'a>,
+    exp: &Exp,
+    palette: Option<&Palette>,
+) -> Result<BlockStyle<'a>> {
     let elems = create_list_with_minlen(exp, 2)?;
     check_symbol(&elems[0], "style")?;
 
     for style in elems.iter().skip(1) {
-        if let Ok(align) = title_align(style) {
-            block = block.title_alignment(align);
-        }
-
-        if let Ok(borders) = borders(style) {
-            block = block.borders(borders);
+        match style
+            .as_list()
+            .and_then(|elems| elems.first())
+            .and_then(|e| e.as_symbol())
+        {
+            Some("title-align") => block = block.title_alignment(title_align(style)?),
+            Some("titles") => {
+                for title in titles(style, palette)? {
+                    block = block.title(title);
+                }
+            }
+            Some("title-position") => block = block.title_position(title_position(style)?),
+            Some("border") => block = block.borders(borders(style, "border")?),
+            Some("border-when-focused") => {
+                block = block.focus_borders(borders(style, "border-when-focused")?)
+            }
+            Some("padding") => block = block.padding(padding(style)?),
+            Some("border-color") => {
+                block = block.border_style(Style::default().fg(border_color(style, palette)?))
+            }
+            Some("bg") => block = block.style(Style::default().bg(background(style, palette)?)),
+            Some("border-type") => block = block.border_type(border_type(style)?),
+            // Applied to the content by `create_block`; only checked here.
+            Some("content-align") => {
+                content_align(style)?;
+            }
+            _ => {}
         }
     }
 
@@ -42,20 +316,44 @@ fn title_align(exp: &Exp) -> Result<Alignment> {
     let elems = create_list_with_len(exp, 2)?;
     check_symbol(&elems[0], "title-align")?;
 
+    create_alignment(&elems[1])
+}
+
+/// `(titles (left "a") (right "b") ...)`, several titles each with its own
+/// alignment.
+fn titles(exp: &Exp, palette: Option<&Palette>) -> Result<Vec<Title<'static>>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "titles")?;
+
+    elems[1..]
+        .iter()
+        .map(|e| aligned_title(e, palette))
+        .collect()
+}
+
+fn aligned_title(exp: &Exp, palette: Option<&Palette>) -> Result<Title<'static>> {
+    let elems = create_list_with_len(exp, 2)?;
+    let alignment = create_alignment(&elems[0])?;
+    let line = create_title(&elems[1], palette)?.unwrap_or_default();
+
+    Ok(Title::from(line).alignment(alignment))
+}
+
+fn title_position(exp: &Exp) -> Result<Position> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "title-position")?;
+
     match elems[1].as_symbol() {
-        Some("center") => Ok(Alignment::Center),
-        Some("left") => Ok(Alignment::Left),
-        Some("right") => Ok(Alignment::Right),
-        _ => Err(RenderTreeError::ExpectedSymbol(
-            "center | left | right",
-            exp.clone(),
-        )),
+        Some("top") => Ok(Position::Top),
+        Some("bottom") => Ok(Position::Bottom),
+        _ => Err(RenderTreeError::ExpectedSymbol("top | bottom", exp.into())),
     }
 }
 
-fn borders(exp: &Exp) -> Result<Borders> {
+/// `(border side)`, or `(border-when-focused side)` when `name` is that.
+fn borders(exp: &Exp, name: &'static str) -> Result<Borders> {
     let elems = create_list_with_len(exp, 2)?;
-    check_symbol(&elems[0], "border")?;
+    check_symbol(&elems[0], name)?;
 
     match elems[1].as_symbol() {
         Some("none") => Ok(Borders::NONE),
@@ -66,11 +364,60 @@ fn borders(exp: &Exp) -> Result<Borders> {
         Some("all") => Ok(Borders::ALL),
         _ => Err(RenderTreeError::ExpectedSymbol(
             "none | left | right | top | bottom | all",
-            exp.clone(),
+            exp.into(),
         )),
     }
 }
 
+fn border_color(exp: &Exp, palette: Option<&Palette>) -> Result<Color> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "border-color")?;
+
+    parse_color(&elems[1], palette)
+}
+
+fn background(exp: &Exp, palette: Option<&Palette>) -> Result<Color> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "bg")?;
+
+    parse_color(&elems[1], palette)
+}
+
+fn border_type(exp: &Exp) -> Result<BorderType> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "border-type")?;
+
+    match elems[1].as_symbol() {
+        Some("plain") => Ok(BorderType::Plain),
+        Some("rounded") => Ok(BorderType::Rounded),
+        Some("double") => Ok(BorderType::Double),
+        Some("thick") => Ok(BorderType::Thick),
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "plain | rounded | double | thick",
+            exp.into(),
+        )),
+    }
+}
+
+fn padding(exp: &Exp) -> Result<Padding> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "padding")?;
+
+    let values = elems
+        .iter()
+        .skip(1)
+        .map(|e| {
+            u16::try_from(create_integer(e)?).map_err(|_| RenderTreeError::NegativeValue(e.into()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    match values[..] {
+        [all] => Ok(Padding::uniform(all)),
+        [left, right, top, bottom] => Ok(Padding::new(left, right, top, bottom)),
+        _ => Err(RenderTreeError::InvalidLength(exp.into())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,18 +431,18 @@ mod tests {
     fn test_create_block() {
         let exp = parse(r#"(block "title" "content")"#);
         assert_eq!(
-            create_block(&exp),
+            create_block(&exp, &RenderContext::default()),
             Ok(RenderTree::Block(
-                Block::new().title("title"),
+                BlockStyle::new().title("title"),
                 Box::new(RenderTree::Text(Text::raw("content")))
             ))
         );
 
         let exp = parse(r#"(block title context (style (title_align center)))"#);
         assert_eq!(
-            create_block(&exp),
+            create_block(&exp, &RenderContext::default()),
             Ok(RenderTree::Block(
-                Block::new()
+                BlockStyle::new()
                     .title("title")
                     .title_alignment(Alignment::Center),
                 Box::new(RenderTree::Text(Text::raw("context")))
@@ -103,18 +450,331 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create_titleless_block() {
+        let exp = parse(r#"(block "content")"#);
+        assert_eq!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Block(
+                BlockStyle::new(),
+                Box::new(RenderTree::Text(Text::raw("content")))
+            ))
+        );
+
+        let exp = parse(r#"(block "" "content")"#);
+        assert_eq!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Block(
+                BlockStyle::new(),
+                Box::new(RenderTree::Text(Text::raw("content")))
+            ))
+        );
+
+        let exp = parse(r#"(layer (block "content" (style (border all))))"#);
+        assert_eq!(
+            crate::UIEngine::render_to_buffer(&exp, 9, 3).unwrap(),
+            ratatui::buffer::Buffer::with_lines(["┌───────┐", "│content│", "└───────┘"])
+        );
+    }
+
+    #[test]
+    fn test_create_span_title() {
+        let exp = parse(r#"(block (span "*" (fg green)) "content")"#);
+        assert_eq!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Block(
+                BlockStyle::new().title(Line::from(Span::styled(
+                    "*",
+                    Style::default().fg(Color::Green)
+                ))),
+                Box::new(RenderTree::Text(Text::raw("content")))
+            ))
+        );
+
+        let exp = parse(r#"(block ((span "*" (fg red)) (span " db")) "content")"#);
+        assert_eq!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Block(
+                BlockStyle::new().title(Line::from(vec![
+                    Span::styled("*", Style::default().fg(Color::Red)),
+                    Span::raw(" db"),
+                ])),
+                Box::new(RenderTree::Text(Text::raw("content")))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_icon_title() {
+        let exp =
+            parse(r#"(layer (block (title "● Connected" (fg green)) "x" (style (border all))))"#);
+        assert_eq!(
+            crate::UIEngine::render_to_lines(&exp, 14, 3).unwrap(),
+            vec!["┌● Connected─┐", "│x           │", "└────────────┘"]
+        );
+
+        let buffer = crate::UIEngine::render_to_buffer(&exp, 14, 3).unwrap();
+        assert_eq!(buffer.get(1, 0).symbol(), "●");
+        for x in 1..12 {
+            assert_eq!(buffer.get(x, 0).fg, Color::Green);
+        }
+        assert_eq!(buffer.get(12, 0).fg, Color::Reset);
+    }
+
+    #[test]
+    fn test_title_position() {
+        let exp =
+            parse(r#"(layer (block "status" "x" (style (border all) (title-position bottom))))"#);
+        assert_eq!(
+            crate::UIEngine::render_to_buffer(&exp, 10, 3).unwrap(),
+            ratatui::buffer::Buffer::with_lines(["┌────────┐", "│x       │", "└status──┘"])
+        );
+
+        let exp = parse(r#"(title-position middle)"#);
+        assert_eq!(
+            title_position(&exp),
+            Err(RenderTreeError::ExpectedSymbol(
+                "top | bottom",
+                exp.clone().into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_multiple_titles() {
+        let exp = parse(r#"(block "main" "x" (style (titles (left "name") (right "ok"))))"#);
+        assert_eq!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Block(
+                BlockStyle::new()
+                    .title("main")
+                    .title(Title::from("name").alignment(Alignment::Left))
+                    .title(Title::from("ok").alignment(Alignment::Right)),
+                Box::new(RenderTree::Text(Text::raw("x")))
+            ))
+        );
+
+        let exp = parse(
+            r#"(layer (block "x" (style (border all) (titles (left "name") (right "ok")))))"#,
+        );
+        assert_eq!(
+            crate::UIEngine::render_to_buffer(&exp, 12, 3).unwrap(),
+            ratatui::buffer::Buffer::with_lines(["┌name────ok┐", "│x         │", "└──────────┘"])
+        );
+    }
+
+    #[test]
+    fn test_block_id() {
+        let exp = parse(r#"(block "t" "content" (id "sidebar"))"#);
+        assert_eq!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Focusable {
+                id: "sidebar".to_string(),
+                focused: false,
+                content: Box::new(RenderTree::Block(
+                    BlockStyle::new().title("t"),
+                    Box::new(RenderTree::Text(Text::raw("content")))
+                )),
+            })
+        );
+
+        let exp = parse(r#"(block "content" (id "sidebar"))"#);
+        assert!(matches!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Focusable { content, .. })
+                if *content == RenderTree::Block(
+                    BlockStyle::new(),
+                    Box::new(RenderTree::Text(Text::raw("content")))
+                )
+        ));
+    }
+
+    #[test]
+    fn test_render_focused_block() {
+        let exp = parse(
+            r#"(layer
+                 (stack horizontal
+                   ((length 3) (block "" "" (style (border all)) (id "a")))
+                   ((length 3) (block "" "" (style (border all)) (id "b")))))"#,
+        );
+        let mut ui =
+            crate::UIEngine::with_backend(ratatui::backend::TestBackend::new(6, 3)).unwrap();
+        ui.set_focus("b");
+        ui.render(&exp).unwrap();
+
+        let buffer = ui.terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).fg, Color::Reset);
+        assert_eq!(buffer.get(3, 0).fg, Color::Yellow);
+        assert_ne!(buffer.get(0, 0).style(), buffer.get(3, 0).style());
+    }
+
+    #[test]
+    fn test_border_when_focused() {
+        let exp = parse(
+            r#"(layer
+                 (stack horizontal
+                   ((length 3) (block "" "a" (style (border-when-focused all)) (id "a")))
+                   ((length 3) (block "" "b" (style (border-when-focused all)) (id "b")))))"#,
+        );
+        let mut ui =
+            crate::UIEngine::with_backend(ratatui::backend::TestBackend::new(6, 3)).unwrap();
+        ui.render(&exp).unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&ratatui::buffer::Buffer::with_lines([
+                "a  b  ", "      ", "      ",
+            ]));
+
+        ui.set_focus("b");
+        ui.render(&exp).unwrap();
+        let buffer = ui.terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).symbol(), "a");
+        assert_eq!(
+            (3..6)
+                .map(|x| buffer.get(x, 0).symbol())
+                .collect::<String>(),
+            "┌─┐"
+        );
+        assert_eq!(buffer.get(4, 1).symbol(), "b");
+
+        ui.set_focus("a");
+        ui.render(&exp).unwrap();
+        let buffer = ui.terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).symbol(), "┌");
+        assert_eq!(buffer.get(1, 1).symbol(), "a");
+        assert_eq!(buffer.get(3, 0).symbol(), "b");
+    }
+
+    #[test]
+    fn test_block_background() {
+        let exp = parse(r##"(layer (block "t" "x" (style (border all) (bg "#202020"))))"##);
+        let buffer = crate::UIEngine::render_to_buffer(&exp, 5, 4).unwrap();
+        let bg = Color::Rgb(0x20, 0x20, 0x20);
+        for (x, y) in [(0, 0), (4, 0), (0, 3), (4, 3), (1, 1), (3, 2)] {
+            assert_eq!(buffer.get(x, y).bg, bg);
+        }
+        assert_eq!(buffer.get(1, 1).symbol(), "x");
+    }
+
+    #[test]
+    fn test_unknown_palette_color() {
+        let palette = Palette::from([("accent".to_string(), Color::Magenta)]);
+        let ctx = RenderContext {
+            palette: Some(&palette),
+            ..RenderContext::default()
+        };
+        for clause in ["border-color", "bg"] {
+            let exp = parse(&format!(r#"(block "t" "x" (style ({} accnet)))"#, clause));
+            assert_eq!(
+                create_block(&exp, &ctx),
+                Err(RenderTreeError::ExpectedSymbol(
+                    "color",
+                    parse("accnet").into()
+                ))
+            );
+        }
+
+        let exp = parse(r#"(block "t" "x" (style (border-color accent)))"#);
+        assert!(create_block(&exp, &ctx).is_ok());
+    }
+
     #[test]
     fn test_create_nested_block() {
         let exp = parse(r#"(block "title" (block "title2" "content"))"#);
         assert_eq!(
-            create_block(&exp),
+            create_block(&exp, &RenderContext::default()),
             Ok(RenderTree::Block(
-                Block::new().title("title"),
+                BlockStyle::new().title("title"),
                 Box::new(RenderTree::Block(
-                    Block::new().title("title2"),
+                    BlockStyle::new().title("title2"),
                     Box::new(RenderTree::Text(Text::raw("content")))
                 ))
             ))
         );
     }
+
+    #[test]
+    fn test_padding() {
+        let exp = parse(r#"(padding 2)"#);
+        assert_eq!(padding(&exp), Ok(Padding::uniform(2)));
+
+        let exp = parse(r#"(padding 1 2 3 4)"#);
+        assert_eq!(padding(&exp), Ok(Padding::new(1, 2, 3, 4)));
+
+        let exp = parse(r#"(padding 1 2)"#);
+        assert_eq!(
+            padding(&exp),
+            Err(RenderTreeError::InvalidLength(exp.clone().into()))
+        );
+
+        let exp = parse(r#"(padding wide)"#);
+        assert_eq!(
+            padding(&exp),
+            Err(RenderTreeError::ExpectInteger(parse("wide").into()))
+        );
+
+        let exp = parse(r#"(padding -1)"#);
+        assert_eq!(
+            padding(&exp),
+            Err(RenderTreeError::NegativeValue(parse("-1").into()))
+        );
+
+        let exp = parse(r#"(block "title" "content" (style (padding wide)))"#);
+        assert_eq!(
+            crate::render_tree::create_render_tree(&exp),
+            Err(RenderTreeError::ExpectInteger(parse("wide").into()))
+        );
+    }
+
+    #[test]
+    fn test_create_block_with_padding() {
+        let exp = parse(r#"(block "title" "content" (style (border all) (padding 1)))"#);
+        assert_eq!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Block(
+                BlockStyle::new()
+                    .title("title")
+                    .borders(Borders::ALL)
+                    .padding(Padding::uniform(1)),
+                Box::new(RenderTree::Text(Text::raw("content")))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_border_color_and_type() {
+        let exp = parse(
+            r#"(block "title" "content"
+                 (style (border all) (border-color cyan) (border-type rounded)))"#,
+        );
+        assert_eq!(
+            create_block(&exp, &RenderContext::default()),
+            Ok(RenderTree::Block(
+                BlockStyle::new()
+                    .title("title")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .border_type(BorderType::Rounded),
+                Box::new(RenderTree::Text(Text::raw("content")))
+            ))
+        );
+
+        let exp = parse(r#"(border-type wavy)"#);
+        assert_eq!(
+            border_type(&exp),
+            Err(RenderTreeError::ExpectedSymbol(
+                "plain | rounded | double | thick",
+                exp.clone().into()
+            ))
+        );
+
+        let exp = parse(r#"(block "title" "content" (style (border all) (border-type wavy)))"#);
+        assert_eq!(
+            crate::render_tree::create_render_tree(&exp),
+            Err(RenderTreeError::ExpectedSymbol(
+                "plain | rounded | double | thick",
+                parse("(border-type wavy)").into()
+            ))
+        );
+    }
 }
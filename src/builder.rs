@@ -0,0 +1,140 @@
+use ratatui::{
+    layout::{Alignment, Constraint, Direction},
+    style::Style,
+    text::Text,
+    widgets::{block::Title, BorderType, Borders, Padding},
+};
+
+use crate::{
+    block::BlockStyle,
+    render_tree::RenderTree,
+    stack::{StackElement, StackOptions},
+};
+
+impl<'a> RenderTree<'a> {
+    pub fn text(text: impl Into<Text<'a>>) -> Self {
+        RenderTree::Text(text.into())
+    }
+
+    pub fn block(block: BlockStyle<'a>, content: RenderTree<'a>) -> Self {
+        RenderTree::Block(block, Box::new(content))
+    }
+
+    pub fn stack(direction: Direction) -> StackBuilder<'a> {
+        StackBuilder {
+            direction,
+            options: StackOptions::default(),
+            elements: Vec::new(),
+        }
+    }
+}
+
+/// Builds a `RenderTree::Stack` one child at a time.
+#[derive(Debug, Clone)]
+pub struct StackBuilder<'a> {
+    direction: Direction,
+    options: StackOptions,
+    elements: Vec<StackElement<'a>>,
+}
+
+impl<'a> StackBuilder<'a> {
+    pub fn options(mut self, options: StackOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn push(mut self, constraint: Constraint, child: RenderTree<'a>) -> Self {
+        self.elements.push(StackElement::new(constraint, child));
+        self
+    }
+
+    pub fn build(self) -> RenderTree<'a> {
+        RenderTree::Stack(self.direction, self.options, self.elements)
+    }
+}
+
+impl<'a> From<StackBuilder<'a>> for RenderTree<'a> {
+    fn from(builder: StackBuilder<'a>) -> Self {
+        builder.build()
+    }
+}
+
+/// Builds a `RenderTree::Block` with the same settings the `(style ...)`
+/// clause of a `(block ...)` node accepts.
+#[derive(Debug, Clone)]
+pub struct BlockBuilder<'a> {
+    block: BlockStyle<'a>,
+}
+
+impl<'a> BlockBuilder<'a> {
+    pub fn new<T: Into<Title<'a>>>(title: T) -> Self {
+        BlockBuilder {
+            block: BlockStyle::new().title(title),
+        }
+    }
+
+    pub fn title_alignment(mut self, alignment: Alignment) -> Self {
+        self.block = self.block.title_alignment(alignment);
+        self
+    }
+
+    pub fn borders(mut self, borders: Borders) -> Self {
+        self.block = self.block.borders(borders);
+        self
+    }
+
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.block = self.block.border_style(style);
+        self
+    }
+
+    pub fn border_type(mut self, border_type: BorderType) -> Self {
+        self.block = self.block.border_type(border_type);
+        self
+    }
+
+    pub fn padding(mut self, padding: Padding) -> Self {
+        self.block = self.block.padding(padding);
+        self
+    }
+
+    pub fn build(self, content: RenderTree<'a>) -> RenderTree<'a> {
+        RenderTree::block(self.block, content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_tree::{create_render_tree_ctx, RenderContext};
+    use ratatui::layout::Rect;
+    use topogi_lang::ast::Exp;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_builder_matches_parsed_tree() {
+        let exp = parse(
+            r#"(stack vertical
+                 ((length 3) (block "title" "content" (style (border all) (title-align center))))
+                 ((fill 1) "body"))"#,
+        );
+        let ctx = RenderContext::new(Rect::new(0, 0, 20, 10));
+
+        let built = RenderTree::stack(Direction::Vertical)
+            .push(
+                Constraint::Length(3),
+                BlockBuilder::new("title")
+                    .borders(Borders::ALL)
+                    .title_alignment(Alignment::Center)
+                    .build(RenderTree::text("content")),
+            )
+            .push(Constraint::Fill(1), RenderTree::text("body"))
+            .build();
+
+        assert_eq!(create_render_tree_ctx(&exp, &ctx), Ok(built));
+    }
+}
@@ -0,0 +1,166 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use ratatui::layout::Rect;
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{RenderLayer, Result};
+
+/// Entries kept before the cache is emptied, so a UI that never repeats a
+/// frame doesn't grow it without bound.
+const CAPACITY: usize = 32;
+
+/// Hashes the structure of `exp`, telling strings and symbols apart.
+pub fn structural_hash(exp: &Exp) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_exp(exp, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_exp(exp: &Exp, state: &mut impl Hasher) {
+    match exp {
+        Exp::Integer(value) => {
+            0u8.hash(state);
+            value.hash(state);
+        }
+        Exp::Symbol(symbol) => {
+            1u8.hash(state);
+            symbol.hash(state);
+        }
+        Exp::String(string) => {
+            2u8.hash(state);
+            string.hash(state);
+        }
+        Exp::List(elems) => {
+            3u8.hash(state);
+            elems.len().hash(state);
+            elems.iter().for_each(|e| hash_exp(e, state));
+        }
+    }
+}
+
+/// Memoizes layers by their source expression and the area they were built
+/// for. Entries are found by hash and keep their expression, so a collision
+/// is a miss rather than the wrong layer.
+#[derive(Debug, Default)]
+pub struct RenderCache {
+    entries: HashMap<(u64, Rect), (Exp, RenderLayer<'static>)>,
+    hits: usize,
+    misses: usize,
+}
+
+impl RenderCache {
+    pub fn new() -> Self {
+        RenderCache::default()
+    }
+
+    /// Returns the layer cached for `exp` in `area`, calling `build` and
+    /// caching its result on a miss.
+    pub fn get_or_build(
+        &mut self,
+        exp: &Exp,
+        area: Rect,
+        build: impl FnOnce() -> Result<RenderLayer<'static>>,
+    ) -> Result<RenderLayer<'static>> {
+        let key = (structural_hash(exp), area);
+        if let Some((_, layer)) = self.entries.get(&key).filter(|(source, _)| source == exp) {
+            self.hits += 1;
+            return Ok(layer.clone());
+        }
+
+        self.misses += 1;
+        let layer = build()?;
+        if self.entries.len() >= CAPACITY {
+            self.entries.clear();
+        }
+        self.entries.insert(key, (exp.clone(), layer.clone()));
+        Ok(layer)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_tree::create_render_layer;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_structural_hash() {
+        let a = parse(r#"(layer (text "a"))"#);
+        assert_eq!(structural_hash(&a), structural_hash(&a.clone()));
+        assert_ne!(
+            structural_hash(&a),
+            structural_hash(&parse(r#"(layer (text "b"))"#))
+        );
+        assert_ne!(
+            structural_hash(&parse(r#"(text "a")"#)),
+            structural_hash(&parse("(text a)"))
+        );
+    }
+
+    #[test]
+    fn test_second_build_is_skipped() {
+        let exp = parse(r#"(layer (block "title" "content"))"#);
+        let area = Rect::new(0, 0, 10, 3);
+        let mut cache = RenderCache::new();
+        let mut builds = 0;
+
+        let first = cache
+            .get_or_build(&exp, area, || {
+                builds += 1;
+                create_render_layer(&exp)
+            })
+            .unwrap();
+        let second = cache
+            .get_or_build(&exp, area, || {
+                builds += 1;
+                create_render_layer(&exp)
+            })
+            .unwrap();
+
+        assert_eq!(builds, 1);
+        assert_eq!(first, second);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        cache
+            .get_or_build(&exp, Rect::new(0, 0, 20, 3), || create_render_layer(&exp))
+            .unwrap();
+        assert_eq!(cache.misses(), 2);
+    }
+
+    #[test]
+    fn test_collision_is_a_miss() {
+        let exp = parse(r#"(layer "a")"#);
+        let other = parse(r#"(layer "b")"#);
+        let area = Rect::new(0, 0, 10, 1);
+        let mut cache = RenderCache::new();
+        let stale = create_render_layer(&other).unwrap();
+        cache
+            .entries
+            .insert((structural_hash(&exp), area), (other, stale));
+
+        let layer = cache
+            .get_or_build(&exp, area, || create_render_layer(&exp))
+            .unwrap();
+        assert_eq!(layer, create_render_layer(&exp).unwrap());
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+    }
+}
@@ -0,0 +1,278 @@
+use ratatui::{
+    style::Color,
+    widgets::canvas::{Canvas, Circle, Context, Line, Rectangle},
+};
+use topogi_lang::ast::Exp;
+
+use crate::{
+    render_tree::{
+        check_symbol, create_float, create_list_with_len, create_list_with_minlen, RenderContext,
+        RenderTree, RenderTreeError, Result,
+    },
+    style::{parse_color, Palette},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CanvasShape {
+    Line {
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        color: Color,
+    },
+    Rectangle {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        color: Color,
+    },
+    Circle {
+        x: f64,
+        y: f64,
+        radius: f64,
+        color: Color,
+    },
+}
+
+impl CanvasShape {
+    fn draw(&self, ctx: &mut Context) {
+        match *self {
+            CanvasShape::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                color,
+            } => ctx.draw(&Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                color,
+            }),
+            CanvasShape::Rectangle {
+                x,
+                y,
+                width,
+                height,
+                color,
+            } => ctx.draw(&Rectangle {
+                x,
+                y,
+                width,
+                height,
+                color,
+            }),
+            CanvasShape::Circle {
+                x,
+                y,
+                radius,
+                color,
+            } => ctx.draw(&Circle {
+                x,
+                y,
+                radius,
+                color,
+            }),
+        }
+    }
+}
+
+/// `(bounds x-min x-max y-min y-max)`
+fn create_bounds(exp: &Exp) -> Result<([f64; 2], [f64; 2])> {
+    let elems = create_list_with_len(exp, 5)?;
+    check_symbol(&elems[0], "bounds")?;
+
+    Ok((
+        [create_float(&elems[1])?, create_float(&elems[2])?],
+        [create_float(&elems[3])?, create_float(&elems[4])?],
+    ))
+}
+
+/// `(line x1 y1 x2 y2 color)`, `(rect x y width height color)` or
+/// `(circle x y radius color)`.
+fn create_shape(exp: &Exp, palette: Option<&Palette>) -> Result<CanvasShape> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    let floats = |len: usize| -> Result<Vec<f64>> {
+        create_list_with_len(exp, len + 2)?;
+        elems[1..=len].iter().map(create_float).collect()
+    };
+
+    match elems[0].as_symbol() {
+        Some("line") => {
+            let v = floats(4)?;
+            Ok(CanvasShape::Line {
+                x1: v[0],
+                y1: v[1],
+                x2: v[2],
+                y2: v[3],
+                color: parse_color(&elems[5], palette)?,
+            })
+        }
+        Some("rect") => {
+            let v = floats(4)?;
+            Ok(CanvasShape::Rectangle {
+                x: v[0],
+                y: v[1],
+                width: v[2],
+                height: v[3],
+                color: parse_color(&elems[5], palette)?,
+            })
+        }
+        Some("circle") => {
+            let v = floats(3)?;
+            Ok(CanvasShape::Circle {
+                x: v[0],
+                y: v[1],
+                radius: v[2],
+                color: parse_color(&elems[4], palette)?,
+            })
+        }
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "line | rect | circle",
+            exp.into(),
+        )),
+    }
+}
+
+/// `(canvas (bounds x-min x-max y-min y-max) shape*)`
+pub fn create_canvas(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "canvas")?;
+
+    let (x_bounds, y_bounds) = create_bounds(&elems[1])?;
+    let shapes = elems[2..]
+        .iter()
+        .map(|e| create_shape(e, ctx.palette))
+        .collect::<Result<_>>()?;
+    Ok(RenderTree::Canvas {
+        x_bounds,
+        y_bounds,
+        shapes,
+    })
+}
+
+pub fn canvas_widget<'s>(
+    x_bounds: [f64; 2],
+    y_bounds: [f64; 2],
+    shapes: &'s [CanvasShape],
+) -> Canvas<'s, impl Fn(&mut Context) + 's> {
+    Canvas::default()
+        .x_bounds(x_bounds)
+        .y_bounds(y_bounds)
+        .paint(move |ctx| shapes.iter().for_each(|shape| shape.draw(ctx)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_line() {
+        assert_eq!(
+            create_shape(&parse("(line 0 0 50 50.5 red)"), None),
+            Ok(CanvasShape::Line {
+                x1: 0.0,
+                y1: 0.0,
+                x2: 50.0,
+                y2: 50.5,
+                color: Color::Red,
+            })
+        );
+        assert_eq!(
+            create_shape(&parse("(line 0 0 50 red)"), None),
+            Err(RenderTreeError::InvalidLength(
+                parse("(line 0 0 50 red)").into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_create_rect() {
+        assert_eq!(
+            create_shape(&parse("(rect 10 10 20 20 blue)"), None),
+            Ok(CanvasShape::Rectangle {
+                x: 10.0,
+                y: 10.0,
+                width: 20.0,
+                height: 20.0,
+                color: Color::Blue,
+            })
+        );
+    }
+
+    #[test]
+    fn test_create_circle() {
+        assert_eq!(
+            create_shape(&parse("(circle 50 50 10 green)"), None),
+            Ok(CanvasShape::Circle {
+                x: 50.0,
+                y: 50.0,
+                radius: 10.0,
+                color: Color::Green,
+            })
+        );
+        assert_eq!(
+            create_shape(&parse("(circle 50 50 ten green)"), None),
+            Err(RenderTreeError::ExpectFloat(parse("ten").into()))
+        );
+    }
+
+    #[test]
+    fn test_create_canvas() {
+        let exp =
+            parse("(canvas (bounds 0 100 0 100) (line 0 0 50 50 red) (rect 10 10 20 20 blue))");
+        assert_eq!(
+            create_canvas(&exp, &RenderContext::default()),
+            Ok(RenderTree::Canvas {
+                x_bounds: [0.0, 100.0],
+                y_bounds: [0.0, 100.0],
+                shapes: vec![
+                    CanvasShape::Line {
+                        x1: 0.0,
+                        y1: 0.0,
+                        x2: 50.0,
+                        y2: 50.0,
+                        color: Color::Red,
+                    },
+                    CanvasShape::Rectangle {
+                        x: 10.0,
+                        y: 10.0,
+                        width: 20.0,
+                        height: 20.0,
+                        color: Color::Blue,
+                    },
+                ],
+            })
+        );
+
+        let exp = parse("(canvas (bounds 0 100 0 100) (triangle 0 0 1 1 red))");
+        assert_eq!(
+            create_canvas(&exp, &RenderContext::default()),
+            Err(RenderTreeError::ExpectedSymbol(
+                "line | rect | circle",
+                parse("(triangle 0 0 1 1 red)").into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render_canvas() {
+        let exp = parse("(layer (canvas (bounds 0 10 0 10) (line 0 0 10 10 red)))");
+        let buffer = UIEngine::render_to_buffer(&exp, 10, 5).unwrap();
+        let drawn = (0..10)
+            .flat_map(|x| (0..5).map(move |y| (x, y)))
+            .filter(|&(x, y)| buffer.get(x, y).symbol() != " ")
+            .count();
+        assert!(drawn > 0);
+    }
+}
@@ -0,0 +1,161 @@
+use ratatui::{
+    style::{Color, Style},
+    symbols::Marker,
+    widgets::{Axis, Chart, Dataset, GraphType},
+};
+use topogi_lang::ast::Exp;
+
+use crate::{
+    render_tree::{
+        check_symbol, create_float, create_list_with_len, create_list_with_minlen, RenderContext,
+        RenderTree, Result,
+    },
+    style::{parse_color, Palette},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChartDataset {
+    pub name: String,
+    pub color: Option<Color>,
+    pub points: Vec<(f64, f64)>,
+}
+
+fn create_point(exp: &Exp) -> Result<(f64, f64)> {
+    let elems = create_list_with_len(exp, 3)?;
+    check_symbol(&elems[0], "point")?;
+
+    Ok((create_float(&elems[1])?, create_float(&elems[2])?))
+}
+
+fn create_color(exp: &Exp, palette: Option<&Palette>) -> Result<Color> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "color")?;
+
+    parse_color(&elems[1], palette)
+}
+
+/// `(dataset "name" [(color c)] (point x y)*)`
+fn create_dataset(exp: &Exp, palette: Option<&Palette>) -> Result<ChartDataset> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "dataset")?;
+
+    let mut points = &elems[2..];
+    let mut color = None;
+    if let Some(Ok(c)) = points.first().map(|e| create_color(e, palette)) {
+        color = Some(c);
+        points = &points[1..];
+    }
+
+    Ok(ChartDataset {
+        name: elems[1].to_string(),
+        color,
+        points: points.iter().map(create_point).collect::<Result<_>>()?,
+    })
+}
+
+pub fn create_chart(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    check_symbol(&elems[0], "chart")?;
+
+    let datasets = elems[1..]
+        .iter()
+        .map(|e| create_dataset(e, ctx.palette))
+        .collect::<Result<_>>()?;
+    Ok(RenderTree::Chart(datasets))
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> [f64; 2] {
+    let [min, max] = values.fold([f64::INFINITY, f64::NEG_INFINITY], |[min, max], v| {
+        [min.min(v), max.max(v)]
+    });
+    if min > max {
+        [0.0, 0.0]
+    } else {
+        [min, max]
+    }
+}
+
+/// A line chart of `datasets` with both axes spanning the data.
+pub fn chart_widget(datasets: &[ChartDataset]) -> Chart<'_> {
+    let points = || datasets.iter().flat_map(|d| d.points.iter());
+    let x_bounds = bounds(points().map(|(x, _)| *x));
+    let y_bounds = bounds(points().map(|(_, y)| *y));
+
+    let datasets = datasets
+        .iter()
+        .map(|d| {
+            let mut dataset = Dataset::default()
+                .name(d.name.as_str())
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .data(&d.points);
+            if let Some(color) = d.color {
+                dataset = dataset.style(Style::default().fg(color));
+            }
+            dataset
+        })
+        .collect();
+
+    Chart::new(datasets)
+        .x_axis(Axis::default().bounds(x_bounds))
+        .y_axis(Axis::default().bounds(y_bounds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{render_tree::RenderTreeError, UIEngine};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_chart() {
+        let exp = parse(
+            r#"(chart
+                 (dataset "cpu" (color red) (point 0 1) (point 1 3.5))
+                 (dataset "mem" (point 0 2)))"#,
+        );
+        assert_eq!(
+            create_chart(&exp, &RenderContext::default()),
+            Ok(RenderTree::Chart(vec![
+                ChartDataset {
+                    name: "cpu".to_string(),
+                    color: Some(Color::Red),
+                    points: vec![(0.0, 1.0), (1.0, 3.5)],
+                },
+                ChartDataset {
+                    name: "mem".to_string(),
+                    color: None,
+                    points: vec![(0.0, 2.0)],
+                },
+            ]))
+        );
+
+        let exp = parse(r#"(chart (dataset "cpu" (point 0 high)))"#);
+        assert_eq!(
+            create_chart(&exp, &RenderContext::default()),
+            Err(RenderTreeError::ExpectFloat(parse("high").into()))
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        assert_eq!(bounds([3.0, -1.0, 2.0].into_iter()), [-1.0, 3.0]);
+        assert_eq!(bounds(std::iter::empty()), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_render_chart() {
+        let exp = parse(r#"(layer (chart (dataset "cpu" (point 0 0) (point 10 10))))"#);
+        let buffer = UIEngine::render_to_buffer(&exp, 20, 10).unwrap();
+        let drawn = (0..20)
+            .flat_map(|x| (0..10).map(move |y| (x, y)))
+            .filter(|&(x, y)| buffer.get(x, y).symbol() != " ")
+            .count();
+        assert!(drawn > 0);
+    }
+}
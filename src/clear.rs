@@ -0,0 +1,41 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{check_symbol, create_list_with_len, RenderTree, Result};
+
+pub fn create_clear(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_len(exp, 1)?;
+    check_symbol(&elems[0], "clear")?;
+
+    Ok(RenderTree::Clear)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+    use ratatui::buffer::Buffer;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_clear() {
+        assert_eq!(create_clear(&parse("(clear)")), Ok(RenderTree::Clear));
+    }
+
+    #[test]
+    fn test_render_clear() {
+        let exp = parse(
+            r#"(layer
+                 (paragraph "xxxxx xxxxx xxxxx" (wrap true))
+                 (float (rect 1 1 3 1)
+                   (stack vertical ((percentage 100) (clear)))))"#,
+        );
+        assert_eq!(
+            UIEngine::render_to_buffer(&exp, 5, 3).unwrap(),
+            Buffer::with_lines(["xxxxx", "x   x", "xxxxx"])
+        );
+    }
+}
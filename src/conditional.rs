@@ -0,0 +1,65 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_list_with_len, create_render_tree_ctx, ErrorContext, RenderContext,
+    RenderTree, RenderTreeError, Result,
+};
+
+/// `(if var then else)`: picks a branch by looking `var` up in the
+/// context's environment. Only the chosen branch is parsed.
+pub fn create_if(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_len(exp, 4)?;
+    check_symbol(&elems[0], "if")?;
+
+    let name = elems[1].as_symbol().ok_or_else(|| {
+        RenderTreeError::ExpectedSymbol("variable", ErrorContext::from(&elems[1]))
+    })?;
+    let value = ctx
+        .env
+        .and_then(|env| env.get(name))
+        .ok_or_else(|| RenderTreeError::UnboundVariable(name.to_string()))?;
+
+    let branch = if *value { &elems[2] } else { &elems[3] };
+    create_render_tree_ctx(branch, ctx).map_err(|e| e.within("if"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_tree::create_render_tree_with_env;
+    use ratatui::text::Text;
+    use std::collections::HashMap;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_if() {
+        let exp = parse(r#"(block "status" (if online "connected" "offline"))"#);
+
+        let env = HashMap::from([("online".to_string(), true)]);
+        let tree = create_render_tree_with_env(&exp, &env).unwrap();
+        assert!(matches!(
+            tree,
+            RenderTree::Block(_, content) if *content == RenderTree::Text(Text::raw("connected"))
+        ));
+
+        let env = HashMap::from([("online".to_string(), false)]);
+        let tree = create_render_tree_with_env(&exp, &env).unwrap();
+        assert!(matches!(
+            tree,
+            RenderTree::Block(_, content) if *content == RenderTree::Text(Text::raw("offline"))
+        ));
+    }
+
+    #[test]
+    fn test_unbound_variable() {
+        let exp = parse(r#"(if online "connected" "offline")"#);
+        assert_eq!(
+            create_render_tree_with_env(&exp, &HashMap::new()),
+            Err(RenderTreeError::UnboundVariable("online".to_string()))
+        );
+    }
+}
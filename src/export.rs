@@ -0,0 +1,142 @@
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+};
+use topogi_lang::ast::Exp;
+
+use crate::{Result, UIEngine};
+
+pub fn render_layer_to_html(exp: &Exp, width: u16, height: u16) -> Result<String> {
+    let buffer = UIEngine::render_to_buffer(exp, width, height)?;
+    Ok(buffer_to_html(&buffer))
+}
+
+pub fn buffer_to_html(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut html = String::from("<pre style=\"font-family: monospace\">");
+
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x < area.right() {
+            let css = cell_css(buffer.get(x, y));
+            let mut text = String::new();
+            while x < area.right() && cell_css(buffer.get(x, y)) == css {
+                push_escaped(&mut text, buffer.get(x, y).symbol());
+                x += 1;
+            }
+
+            if css.is_empty() {
+                html.push_str(&text);
+            } else {
+                html.push_str(&format!("<span style=\"{}\">{}</span>", css, text));
+            }
+        }
+        html.push('\n');
+    }
+
+    html.push_str("</pre>");
+    html
+}
+
+fn cell_css(cell: &Cell) -> String {
+    let (fg, bg) = if cell.modifier.contains(Modifier::REVERSED) {
+        (cell.bg, cell.fg)
+    } else {
+        (cell.fg, cell.bg)
+    };
+
+    let mut css = Vec::new();
+    if let Some(color) = css_color(fg) {
+        css.push(format!("color: {}", color));
+    }
+    if let Some(color) = css_color(bg) {
+        css.push(format!("background-color: {}", color));
+    }
+    if cell.modifier.contains(Modifier::BOLD) {
+        css.push("font-weight: bold".to_string());
+    }
+    if cell.modifier.contains(Modifier::ITALIC) {
+        css.push("font-style: italic".to_string());
+    }
+    if cell.modifier.contains(Modifier::DIM) {
+        css.push("opacity: 0.5".to_string());
+    }
+    match (
+        cell.modifier.contains(Modifier::UNDERLINED),
+        cell.modifier.contains(Modifier::CROSSED_OUT),
+    ) {
+        (true, true) => css.push("text-decoration: underline line-through".to_string()),
+        (true, false) => css.push("text-decoration: underline".to_string()),
+        (false, true) => css.push("text-decoration: line-through".to_string()),
+        (false, false) => {}
+    }
+
+    css.join("; ")
+}
+
+fn css_color(color: Color) -> Option<String> {
+    let hex = match color {
+        Color::Black => "#000000",
+        Color::Red => "#800000",
+        Color::Green => "#008000",
+        Color::Yellow => "#808000",
+        Color::Blue => "#000080",
+        Color::Magenta => "#800080",
+        Color::Cyan => "#008080",
+        Color::Gray => "#c0c0c0",
+        Color::DarkGray => "#808080",
+        Color::LightRed => "#ff0000",
+        Color::LightGreen => "#00ff00",
+        Color::LightYellow => "#ffff00",
+        Color::LightBlue => "#0000ff",
+        Color::LightMagenta => "#ff00ff",
+        Color::LightCyan => "#00ffff",
+        Color::White => "#ffffff",
+        Color::Rgb(r, g, b) => return Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        Color::Reset | Color::Indexed(_) => return None,
+    };
+    Some(hex.to_string())
+}
+
+fn push_escaped(html: &mut String, symbol: &str) {
+    for c in symbol.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            _ => html.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_buffer_to_html() {
+        let mut buffer = Buffer::with_lines(vec!["a<b"]);
+        buffer.get_mut(0, 0).set_fg(Color::Rgb(255, 0, 0));
+        buffer.get_mut(2, 0).modifier = Modifier::BOLD;
+
+        assert_eq!(
+            buffer_to_html(&buffer),
+            "<pre style=\"font-family: monospace\">\
+             <span style=\"color: #ff0000\">a</span>&lt;\
+             <span style=\"font-weight: bold\">b</span>\n</pre>"
+        );
+    }
+
+    #[test]
+    fn test_render_layer_to_html() {
+        let exp = parse(r#"(layer (block "t" "hi" (style (border all))))"#);
+        let html = render_layer_to_html(&exp, 6, 3).unwrap();
+        assert!(html.starts_with("<pre"));
+        assert!(html.contains("│hi  │"));
+        assert_eq!(html.matches('\n').count(), 3);
+    }
+}
@@ -0,0 +1,98 @@
+use ratatui::layout::Rect;
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_integer, create_list_with_len, create_render_tree_ctx, RenderContext,
+    RenderTree, Result,
+};
+
+fn create_rect(exp: &Exp) -> Result<Rect> {
+    let elems = create_list_with_len(exp, 5)?;
+    check_symbol(&elems[0], "rect")?;
+
+    let x = create_integer(&elems[1])?;
+    let y = create_integer(&elems[2])?;
+    let width = create_integer(&elems[3])?;
+    let height = create_integer(&elems[4])?;
+
+    Ok(Rect::new(x as u16, y as u16, width as u16, height as u16))
+}
+
+pub fn create_float_node(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_len(exp, 3)?;
+    check_symbol(&elems[0], "float")?;
+
+    let area = create_rect(&elems[1])?;
+    let content_ctx = RenderContext {
+        area: float_area(area, ctx.area),
+        ..*ctx
+    };
+    let content = create_render_tree_ctx(&elems[2], &content_ctx).map_err(|e| e.within("float"))?;
+
+    Ok(RenderTree::Float {
+        area,
+        content: Box::new(content),
+    })
+}
+
+/// Resolves a float's rect, given relative to the parent area, into an
+/// absolute rect clipped to the parent.
+pub fn float_area(float: Rect, parent: Rect) -> Rect {
+    Rect::new(
+        parent.x.saturating_add(float.x),
+        parent.y.saturating_add(float.y),
+        float.width,
+        float.height,
+    )
+    .intersection(parent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+    use ratatui::{buffer::Buffer, text::Text};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_float() {
+        let exp = parse(r#"(float (rect 2 1 10 3) "popup")"#);
+        assert_eq!(
+            create_float_node(&exp, &RenderContext::default()),
+            Ok(RenderTree::Float {
+                area: Rect::new(2, 1, 10, 3),
+                content: Box::new(RenderTree::Text(Text::raw("popup")))
+            })
+        );
+    }
+
+    #[test]
+    fn test_float_area() {
+        let parent = Rect::new(5, 5, 10, 10);
+        assert_eq!(
+            float_area(Rect::new(2, 1, 3, 3), parent),
+            Rect::new(7, 6, 3, 3)
+        );
+        assert_eq!(
+            float_area(Rect::new(8, 8, 5, 5), parent),
+            Rect::new(13, 13, 2, 2)
+        );
+    }
+
+    #[test]
+    fn test_render_float_over_base() {
+        let exp = parse(
+            r#"(layer
+                 (block "" "" (style (border all)))
+                 (float (rect 2 1 3 1) "abc"))"#,
+        );
+        assert_eq!(
+            UIEngine::render_to_buffer(&exp, 8, 3).unwrap(),
+            Buffer::with_lines(["┌──────┐", "│ abc  │", "└──────┘"])
+        );
+    }
+}
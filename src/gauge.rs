@@ -0,0 +1,84 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_float, create_list_with_minlen, RenderTree, RenderTreeError, Result,
+};
+
+pub fn create_gauge(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "gauge")?;
+    if elems.len() > 3 {
+        return Err(RenderTreeError::InvalidLength(exp.into()));
+    }
+
+    // `create_float` rejects NaN, which would get past the clamp and make
+    // ratatui's gauge panic.
+    let ratio = create_float(&elems[1])?.clamp(0.0, 1.0);
+    let label = elems.get(2).map(|e| e.to_string());
+
+    Ok(RenderTree::Gauge { ratio, label })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_tree;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_gauge() {
+        let exp = parse(r#"(gauge 0.75 "75%")"#);
+        assert_eq!(
+            create_gauge(&exp),
+            Ok(RenderTree::Gauge {
+                ratio: 0.75,
+                label: Some("75%".to_string())
+            })
+        );
+
+        let exp = parse(r#"(gauge 2)"#);
+        assert_eq!(
+            create_gauge(&exp),
+            Ok(RenderTree::Gauge {
+                ratio: 1.0,
+                label: None
+            })
+        );
+
+        let exp = parse(r#"(gauge half)"#);
+        assert_eq!(
+            create_gauge(&exp),
+            Err(RenderTreeError::ExpectFloat(parse("half").into()))
+        );
+    }
+
+    #[test]
+    fn test_render_gauge() {
+        let tree = create_gauge(&parse(r#"(gauge 0.5 "half")"#)).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(10, 1)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let row = (0..10)
+            .map(|x| buffer.get(x, 0).symbol())
+            .collect::<String>();
+        assert!(row.contains("half"));
+    }
+
+    #[test]
+    fn test_render_gauge_nan() {
+        for source in ["(layer (gauge nan))", "(layer (vgauge nan))"] {
+            assert!(crate::UIEngine::render_to_buffer(&parse(source), 10, 1).is_err());
+        }
+    }
+}
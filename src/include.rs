@@ -0,0 +1,142 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{check_symbol, create_list_with_len, RenderTreeError, Result};
+
+fn include_path(exp: &Exp) -> Result<String> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "include")?;
+
+    Ok(elems[1].to_string())
+}
+
+/// Reads and parses `path`, splicing in any `(include "file")` forms it
+/// contains. Include paths are resolved relative to the including file.
+pub fn load_file(path: &Path) -> Result<Exp> {
+    load(path, &mut HashSet::new())
+}
+
+/// Splices `(include "file")` forms in `exp`, resolving paths against
+/// `base_dir`.
+pub fn resolve_includes(exp: &Exp, base_dir: &Path) -> Result<Exp> {
+    resolve(exp, base_dir, &mut HashSet::new())
+}
+
+fn load(path: &Path, visiting: &mut HashSet<PathBuf>) -> Result<Exp> {
+    let include_failed =
+        |reason: String| RenderTreeError::IncludeFailed(path.display().to_string(), reason);
+
+    let canonical = path
+        .canonicalize()
+        .map_err(|err| include_failed(err.to_string()))?;
+    if !visiting.insert(canonical.clone()) {
+        return Err(RenderTreeError::IncludeCycle(path.display().to_string()));
+    }
+
+    let source = fs::read_to_string(&canonical).map_err(|err| include_failed(err.to_string()))?;
+    let mut parser = topogi_lang::parser::Parser::new(&source);
+    let exp = parser
+        .parse_exp()
+        .map_err(|err| include_failed(format!("{:?}", err)))?;
+
+    let base_dir = canonical.parent().unwrap_or(Path::new("."));
+    let resolved = resolve(&exp, base_dir, visiting);
+    visiting.remove(&canonical);
+    resolved
+}
+
+fn resolve(exp: &Exp, base_dir: &Path, visiting: &mut HashSet<PathBuf>) -> Result<Exp> {
+    let Some(elems) = exp.as_list() else {
+        return Ok(exp.clone());
+    };
+
+    if elems.first().and_then(|e| e.as_symbol()) == Some("include") {
+        return load(&base_dir.join(include_path(exp)?), visiting);
+    }
+
+    let elems = elems
+        .iter()
+        .map(|e| resolve(e, base_dir, visiting))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Exp::List(elems))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    /// A directory unique to one test, removed when dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            static NEXT: AtomicUsize = AtomicUsize::new(0);
+            let dir = std::env::temp_dir().join(format!(
+                "topogi-include-{}-{}-{}",
+                name,
+                std::process::id(),
+                NEXT.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+
+        fn join(&self, path: &str) -> PathBuf {
+            self.0.join(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_resolve_includes() {
+        let dir = TempDir::new("splice");
+        fs::write(dir.join("header.topogi"), r#"(block "header" "content")"#).unwrap();
+        fs::write(
+            dir.join("main.topogi"),
+            r#"(layer (include "header.topogi") "footer")"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            load_file(&dir.join("main.topogi")),
+            Ok(parse(r#"(layer (block "header" "content") "footer")"#))
+        );
+    }
+
+    #[test]
+    fn test_include_cycle() {
+        let dir = TempDir::new("cycle");
+        fs::write(dir.join("a.topogi"), r#"(layer (include "b.topogi"))"#).unwrap();
+        fs::write(dir.join("b.topogi"), r#"(block "b" (include "a.topogi"))"#).unwrap();
+
+        assert!(matches!(
+            load_file(&dir.join("a.topogi")),
+            Err(RenderTreeError::IncludeCycle(_))
+        ));
+    }
+
+    #[test]
+    fn test_include_missing_file() {
+        let exp = parse(r#"(layer (include "missing.topogi"))"#);
+        assert!(matches!(
+            resolve_includes(&exp, &TempDir::new("missing").0),
+            Err(RenderTreeError::IncludeFailed(_, _))
+        ));
+    }
+}
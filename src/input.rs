@@ -0,0 +1,234 @@
+use topogi_lang::ast::Exp;
+use unicode_width::UnicodeWidthChar;
+
+use crate::render_tree::{
+    check_symbol, create_integer, create_list_with_len, create_list_with_minlen, RenderTree,
+    RenderTreeError, Result,
+};
+
+/// The text and cursor of an input field, kept by the engine between renders
+/// so edits survive the tree being rebuilt.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct InputState {
+    pub value: String,
+    pub cursor: usize,
+}
+
+impl InputState {
+    pub fn insert_char(&mut self, c: char) {
+        let at = byte_index(&self.value, self.cursor);
+        self.value.insert(at, c);
+        self.cursor += 1;
+    }
+
+    /// Deletes the character before the cursor.
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+        self.cursor -= 1;
+        self.value.remove(byte_index(&self.value, self.cursor));
+    }
+
+    /// Moves the cursor `delta` characters, staying within the value.
+    pub fn move_cursor(&mut self, delta: isize) {
+        self.cursor = self
+            .cursor
+            .saturating_add_signed(delta)
+            .min(self.value.chars().count());
+    }
+}
+
+fn byte_index(value: &str, cursor: usize) -> usize {
+    value
+        .char_indices()
+        .nth(cursor)
+        .map_or(value.len(), |(i, _)| i)
+}
+
+fn create_value(exp: &Exp) -> Result<String> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "value")?;
+
+    Ok(elems[1].to_string())
+}
+
+fn create_cursor(exp: &Exp) -> Result<usize> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "cursor")?;
+
+    Ok(create_integer(&elems[1])?.max(0) as usize)
+}
+
+/// `(input [(value "text")] [(cursor n)])`. The cursor defaults to the end of
+/// the value and is clamped to it.
+pub fn create_input(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    check_symbol(&elems[0], "input")?;
+
+    let mut value = String::new();
+    let mut cursor = None;
+    for clause in &elems[1..] {
+        if let Ok(v) = create_value(clause) {
+            value = v;
+        } else if let Ok(c) = create_cursor(clause) {
+            cursor = Some(c);
+        } else {
+            return Err(RenderTreeError::ExpectedSymbol(
+                "value | cursor",
+                clause.into(),
+            ));
+        }
+    }
+
+    let len = value.chars().count();
+    Ok(RenderTree::Input {
+        value,
+        cursor: cursor.unwrap_or(len).min(len),
+        active: false,
+    })
+}
+
+/// Finds the first input field in `tree`, returning its value, cursor and
+/// whether it is active.
+pub fn input_field<'t>(
+    tree: &'t mut RenderTree<'_>,
+) -> Option<(&'t mut String, &'t mut usize, &'t mut bool)> {
+    match tree {
+        RenderTree::Input {
+            value,
+            cursor,
+            active,
+        } => Some((value, cursor, active)),
+        RenderTree::Block(_, content)
+        | RenderTree::Float { content, .. }
+        | RenderTree::Aligned { content, .. }
+        | RenderTree::Focusable { content, .. }
+        | RenderTree::Blink { content, .. } => input_field(content),
+        RenderTree::Stack(_, _, stack_elems) => stack_elems
+            .iter_mut()
+            .find_map(|e| input_field(&mut e.content)),
+        RenderTree::Responsive(_, content, otherwise) => {
+            input_field(content).or_else(|| otherwise.as_mut().and_then(|o| input_field(o)))
+        }
+        _ => None,
+    }
+}
+
+/// The part of `value` shown in a field `width` columns wide, scrolled so the
+/// cursor stays visible, and the cursor's column within it. Wide characters
+/// take up two columns.
+pub fn input_window(value: &str, cursor: usize, width: usize) -> (String, usize) {
+    let chars: Vec<char> = value.chars().collect();
+    let char_width = |c: &char| c.width().unwrap_or(0);
+    let cursor = cursor.min(chars.len());
+
+    let mut offset = 0;
+    let mut column: usize = chars[..cursor].iter().map(char_width).sum();
+    while offset < cursor && column + 1 > width {
+        column -= char_width(&chars[offset]);
+        offset += 1;
+    }
+
+    let mut used = 0;
+    let visible = chars[offset..]
+        .iter()
+        .take_while(|c| {
+            used += char_width(c);
+            used <= width
+        })
+        .collect();
+    (visible, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_tree;
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_input() {
+        let exp = parse(r#"(input (value "hello") (cursor 2))"#);
+        assert_eq!(
+            create_input(&exp),
+            Ok(RenderTree::Input {
+                value: "hello".to_string(),
+                cursor: 2,
+                active: false
+            })
+        );
+
+        let exp = parse(r#"(input (value "hello") (cursor 99))"#);
+        assert_eq!(
+            create_input(&exp),
+            Ok(RenderTree::Input {
+                value: "hello".to_string(),
+                cursor: 5,
+                active: false
+            })
+        );
+
+        assert_eq!(
+            create_input(&parse("(input)")),
+            Ok(RenderTree::Input {
+                value: String::new(),
+                cursor: 0,
+                active: false
+            })
+        );
+    }
+
+    #[test]
+    fn test_input_state() {
+        let mut state = InputState {
+            value: "hllo".to_string(),
+            cursor: 1,
+        };
+        state.insert_char('e');
+        assert_eq!(state.value, "hello");
+        assert_eq!(state.cursor, 2);
+
+        state.move_cursor(10);
+        assert_eq!(state.cursor, 5);
+        state.backspace();
+        assert_eq!(state.value, "hell");
+
+        state.move_cursor(-10);
+        assert_eq!(state.cursor, 0);
+        state.backspace();
+        assert_eq!(state.value, "hell");
+    }
+
+    #[test]
+    fn test_input_window() {
+        assert_eq!(input_window("hello", 5, 10), ("hello".to_string(), 5));
+        assert_eq!(input_window("hello world", 11, 5), ("orld".to_string(), 4));
+        assert_eq!(input_window("你好世界", 1, 5), ("你好".to_string(), 2));
+        assert_eq!(input_window("你好世界", 4, 5), ("世界".to_string(), 4));
+    }
+
+    #[test]
+    fn test_render_input() {
+        let mut tree = create_input(&parse(r#"(input (value "abc") (cursor 1))"#)).unwrap();
+        if let Some((_, _, active)) = input_field(&mut tree) {
+            *active = true;
+        }
+        let mut terminal = Terminal::new(TestBackend::new(5, 1)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["abc  "]));
+        assert_eq!(terminal.get_cursor().unwrap(), (1, 0));
+    }
+}
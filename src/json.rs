@@ -0,0 +1,193 @@
+use ratatui::{
+    layout::{Constraint, Direction},
+    text::Text,
+};
+use serde_json::Value;
+
+use crate::{
+    block::BlockStyle,
+    render_tree::{RenderTree, RenderTreeError, Result},
+    stack::{StackElement, StackOptions},
+};
+
+fn invalid(message: &str, value: &Value) -> RenderTreeError {
+    RenderTreeError::InvalidJson(format!("{}, found {}", message, value))
+}
+
+fn field<'v>(object: &'v Value, name: &str) -> Result<&'v Value> {
+    object
+        .get(name)
+        .ok_or_else(|| invalid(&format!("expected field '{}'", name), object))
+}
+
+fn string(value: &Value) -> Result<&str> {
+    value
+        .as_str()
+        .ok_or_else(|| invalid("expected a string", value))
+}
+
+fn integer(value: &Value) -> Result<u16> {
+    value
+        .as_u64()
+        .and_then(|n| u16::try_from(n).ok())
+        .ok_or_else(|| invalid("expected an integer from 0 to 65535", value))
+}
+
+/// Builds a tree from JSON instead of topogi source. A string is text; an
+/// object has a single key naming the node kind:
+///
+/// - `{"text": "hi"}`
+/// - `{"block": {"title": "x", "content": ...}}`, `title` optional
+/// - `{"stack": {"direction": "vertical", "children": [{"length": 3, "content": ...}]}}`
+/// - `{"list": ["a", "b"]}`
+pub fn from_json(value: &Value) -> Result<RenderTree<'static>> {
+    if let Some(text) = value.as_str() {
+        return Ok(RenderTree::Text(Text::raw(text.to_string())));
+    }
+
+    let (kind, body) = match value.as_object() {
+        Some(object) if object.len() == 1 => object.iter().next().unwrap(),
+        _ => return Err(invalid("expected a string or single-key object", value)),
+    };
+    match kind.as_str() {
+        "text" => Ok(RenderTree::Text(Text::raw(string(body)?.to_string()))),
+        "block" => from_json_block(body),
+        "stack" => from_json_stack(body),
+        "list" => from_json_list(body),
+        _ => Err(invalid("expected a node kind", value)),
+    }
+}
+
+fn from_json_block(body: &Value) -> Result<RenderTree<'static>> {
+    let mut block = BlockStyle::new();
+    if let Some(title) = body.get("title") {
+        let title = string(title)?;
+        if !title.is_empty() {
+            block = block.title(title.to_string());
+        }
+    }
+    let content = from_json(field(body, "content")?)?;
+
+    Ok(RenderTree::Block(block, Box::new(content)))
+}
+
+fn from_json_direction(value: &Value) -> Result<Direction> {
+    match string(value)? {
+        "horizontal" => Ok(Direction::Horizontal),
+        "vertical" => Ok(Direction::Vertical),
+        direction => Err(RenderTreeError::InvalidDirection(direction.to_string())),
+    }
+}
+
+fn from_json_constraint(child: &Value) -> Result<Constraint> {
+    let constraints: [(&str, fn(u16) -> Constraint); 5] = [
+        ("length", Constraint::Length),
+        ("min", Constraint::Min),
+        ("max", Constraint::Max),
+        ("percentage", Constraint::Percentage),
+        ("fill", Constraint::Fill),
+    ];
+    constraints
+        .iter()
+        .find_map(|(name, constraint)| child.get(*name).map(|n| integer(n).map(constraint)))
+        .unwrap_or_else(|| Err(invalid("expected a constraint", child)))
+}
+
+fn from_json_stack(body: &Value) -> Result<RenderTree<'static>> {
+    let direction = from_json_direction(field(body, "direction")?)?;
+    let children = field(body, "children")?;
+    let children = children
+        .as_array()
+        .ok_or_else(|| invalid("expected an array", children))?;
+
+    let stack_elements = children
+        .iter()
+        .map(|child| {
+            Ok(StackElement::new(
+                from_json_constraint(child)?,
+                from_json(field(child, "content")?)?,
+            ))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(RenderTree::Stack(
+        direction,
+        StackOptions::default(),
+        stack_elements,
+    ))
+}
+
+fn from_json_list(body: &Value) -> Result<RenderTree<'static>> {
+    let items = body
+        .as_array()
+        .ok_or_else(|| invalid("expected an array", body))?
+        .iter()
+        .map(|item| string(item).map(str::to_string))
+        .collect::<Result<_>>()?;
+
+    Ok(RenderTree::List {
+        items,
+        highlight_symbol: None,
+        focused: false,
+        selected: None,
+        zebra: None,
+        show_count: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_tree::create_render_tree;
+    use serde_json::json;
+    use topogi_lang::ast::Exp;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_json_matches_dsl() {
+        let value = json!({"block": {"title": "x", "content": {"text": "hi"}}});
+        assert_eq!(
+            from_json(&value),
+            create_render_tree(&parse(r#"(block "x" (text "hi"))"#))
+        );
+
+        let value = json!({"stack": {
+            "direction": "vertical",
+            "children": [
+                {"length": 3, "content": {"block": {"content": "a"}}},
+                {"fill": 1, "content": {"list": ["b", "c"]}}
+            ]
+        }});
+        assert_eq!(
+            from_json(&value),
+            create_render_tree(&parse(
+                r#"(stack vertical
+                     ((length 3) (block "a"))
+                     ((fill 1) (list "b" "c")))"#
+            ))
+        );
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        assert!(matches!(
+            from_json(&json!({"gauge": 0.5})),
+            Err(RenderTreeError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            from_json(&json!({"stack": {
+                "direction": "vertical",
+                "children": [{"length": 70000, "content": "a"}]
+            }})),
+            Err(RenderTreeError::InvalidJson(_))
+        ));
+        assert_eq!(
+            from_json(&json!({"stack": {"direction": "diagonal", "children": []}})),
+            Err(RenderTreeError::InvalidDirection("diagonal".to_string()))
+        );
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use ratatui::layout::{Constraint, Direction, Flex, Layout, Rect};
+
+pub fn split_named(
+    area: Rect,
+    spec: &[(String, Constraint)],
+    dir: Direction,
+) -> HashMap<String, Rect> {
+    let constraints = spec.iter().map(|(_, c)| *c).collect::<Vec<_>>();
+    let layout = Layout::default()
+        .direction(dir)
+        .constraints(constraints)
+        .split(area);
+
+    spec.iter()
+        .zip(layout.iter())
+        .map(|((name, _), area)| (name.clone(), *area))
+        .collect()
+}
+
+/// Centers a rect sized by `horizontal` x `vertical` within `area`.
+pub fn center_rect(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
+    let [area] = Layout::horizontal([horizontal])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([vertical]).flex(Flex::Center).areas(area);
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_named_horizontal() {
+        let spec = vec![
+            ("sidebar".to_string(), Constraint::Length(10)),
+            ("main".to_string(), Constraint::Fill(1)),
+        ];
+        let regions = split_named(Rect::new(0, 0, 40, 10), &spec, Direction::Horizontal);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions["sidebar"], Rect::new(0, 0, 10, 10));
+        assert_eq!(regions["main"], Rect::new(10, 0, 30, 10));
+    }
+
+    #[test]
+    fn test_center_rect() {
+        let area = Rect::new(0, 0, 20, 10);
+        assert_eq!(
+            center_rect(area, Constraint::Length(6), Constraint::Length(4)),
+            Rect::new(7, 3, 6, 4)
+        );
+        assert_eq!(
+            center_rect(area, Constraint::Percentage(50), Constraint::Percentage(20)),
+            Rect::new(5, 4, 10, 2)
+        );
+        assert_eq!(
+            center_rect(area, Constraint::Fill(1), Constraint::Length(1)),
+            Rect::new(0, 4, 20, 1)
+        );
+        assert_eq!(
+            center_rect(
+                Rect::new(10, 5, 4, 2),
+                Constraint::Length(2),
+                Constraint::Length(2)
+            ),
+            Rect::new(11, 5, 2, 2)
+        );
+    }
+
+    #[test]
+    fn test_split_named_vertical() {
+        let spec = vec![
+            ("header".to_string(), Constraint::Length(3)),
+            ("body".to_string(), Constraint::Percentage(50)),
+            ("footer".to_string(), Constraint::Fill(1)),
+        ];
+        let regions = split_named(Rect::new(0, 0, 20, 20), &spec, Direction::Vertical);
+        assert_eq!(regions["header"], Rect::new(0, 0, 20, 3));
+        assert_eq!(regions["body"], Rect::new(0, 3, 20, 10));
+        assert_eq!(regions["footer"], Rect::new(0, 13, 20, 7));
+    }
+}
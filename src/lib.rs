@@ -1,25 +1,98 @@
+pub mod align;
+pub mod blink;
 pub mod block;
+pub mod builder;
+pub mod cache;
+pub mod canvas;
+pub mod chart;
+pub mod clear;
+pub mod conditional;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod float;
+pub mod gauge;
+pub mod include;
+pub mod input;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod layout;
+pub mod list;
+pub mod log;
+pub mod marquee;
+pub mod paragraph;
+pub mod pretty;
 pub mod render_tree;
 pub mod renderer;
+pub mod responsive;
+pub mod scrollbar;
+#[cfg(feature = "serde")]
+pub mod serialize;
+pub mod spacer;
+pub mod sparkline;
 pub mod stack;
+pub mod style;
+pub mod table;
+pub mod tabs;
+pub mod template;
 pub mod text;
+pub mod vgauge;
 
+use blink::apply_blink;
+use block::apply_focus;
+use cache::RenderCache;
+use input::{input_field, InputState};
+use layout::center_rect;
+use list::{focused_list, next_selection, prev_selection};
 use ratatui::{
-    backend::CrosstermBackend,
+    backend::{Backend, CrosstermBackend, TestBackend},
+    buffer::Buffer,
     crossterm::{
+        event::{self, DisableMouseCapture, EnableMouseCapture, Event},
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
         ExecutableCommand,
     },
-    Terminal,
+    layout::{Constraint, Rect},
+    style::Color,
+    widgets::Clear,
+    Frame, Terminal,
 };
-use render_tree::create_render_layer;
-use renderer::render_layer;
-use std::io::{stdout, Stdout};
+use render_tree::{
+    create_render_layer_ctx, create_render_tree_ctx, RenderContext, RenderLayer, RenderTree,
+};
+use renderer::{
+    chrome_areas, layer_overflows, render_layer, render_too_small, render_tree, OverflowReport,
+};
+use stack::validate_constraints;
+use std::{
+    collections::HashMap,
+    io::{stdout, Stdout},
+    path::Path,
+    time::Duration,
+};
+use style::Palette;
+use template::expand_repeats;
 use topogi_lang::ast::Exp;
 
 #[derive(Debug)]
-pub struct UIEngine {
-    pub terminal: Terminal<CrosstermBackend<Stdout>>,
+pub struct UIEngine<B: Backend = CrosstermBackend<Stdout>> {
+    pub terminal: Terminal<B>,
+    track_overflow: bool,
+    overflows: Vec<OverflowReport>,
+    mouse_capture: bool,
+    validate_constraints: bool,
+    scroll: (u16, u16),
+    selection: Option<usize>,
+    selection_len: usize,
+    focus: Option<String>,
+    cache: Option<RenderCache>,
+    input: Option<InputState>,
+    /// The `(value ...)` and `(cursor ...)` the input state was seeded from.
+    input_seed: Option<(String, usize)>,
+    wrap_selection: bool,
+    palette: Palette,
+    /// Layers built so far, which drives `(blink)` text.
+    tick: u64,
+    last_frame: Option<(Rect, RenderLayer<'static>)>,
 }
 
 #[derive(Debug)]
@@ -28,6 +101,24 @@ pub enum RenderError {
     IO(std::io::Error),
 }
 
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderError::RenderTreeError(err) => write!(f, "invalid render tree: {}", err),
+            RenderError::IO(err) => write!(f, "terminal io error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RenderError::RenderTreeError(err) => Some(err),
+            RenderError::IO(err) => Some(err),
+        }
+    }
+}
+
 impl From<std::io::Error> for RenderError {
     fn from(err: std::io::Error) -> Self {
         RenderError::IO(err)
@@ -40,23 +131,820 @@ impl UIEngine {
     pub fn new() -> Result<Self> {
         stdout().execute(EnterAlternateScreen)?;
         enable_raw_mode()?;
-        let mut terminal = Terminal::new(CrosstermBackend::new(stdout())).unwrap();
-        terminal.clear()?;
-        Ok(UIEngine { terminal })
+        stdout().execute(EnableMouseCapture)?;
+        let mut engine = UIEngine::with_backend(CrosstermBackend::new(stdout()))?;
+        engine.mouse_capture = true;
+        engine.terminal.clear()?;
+        Ok(engine)
+    }
+
+    /// Renders `exp` into an off-screen buffer without touching the terminal,
+    /// for asserting on layouts in tests.
+    pub fn render_to_buffer(exp: &Exp, width: u16, height: u16) -> Result<Buffer> {
+        let ctx = RenderContext::new(Rect::new(0, 0, width, height));
+        let layer = create_render_layer_ctx(exp, &ctx).map_err(RenderError::RenderTreeError)?;
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        terminal.draw(|frame| {
+            let area = frame.size();
+            render_layer(&layer, frame, area);
+        })?;
+        Ok(terminal.backend().buffer().clone())
+    }
+
+    /// Like `render_to_buffer`, but returns each row as plain text with
+    /// styling and trailing spaces stripped, for golden-file tests and logs.
+    pub fn render_to_lines(exp: &Exp, width: u16, height: u16) -> Result<Vec<String>> {
+        let buffer = Self::render_to_buffer(exp, width, height)?;
+        Ok((0..height)
+            .map(|y| {
+                let row = (0..width)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>();
+                row.trim_end_matches(' ').to_string()
+            })
+            .collect())
+    }
+
+    pub fn shutdown(&self) -> Result<()> {
+        // Restore every piece of terminal state even if an earlier step fails.
+        let mouse = if self.mouse_capture {
+            stdout().execute(DisableMouseCapture).map(|_| ())
+        } else {
+            Ok(())
+        };
+        let screen = stdout().execute(LeaveAlternateScreen).map(|_| ());
+        let raw_mode = disable_raw_mode();
+
+        mouse.and(screen).and(raw_mode)?;
+        Ok(())
+    }
+}
+
+impl<B: Backend> UIEngine<B> {
+    /// Builds an engine on top of `backend` without touching stdout, for
+    /// tests and non-TTY environments.
+    pub fn with_backend(backend: B) -> Result<Self> {
+        Ok(UIEngine {
+            terminal: Terminal::new(backend)?,
+            track_overflow: false,
+            overflows: Vec::new(),
+            mouse_capture: false,
+            validate_constraints: false,
+            scroll: (0, 0),
+            selection: None,
+            selection_len: 0,
+            focus: None,
+            cache: None,
+            input: None,
+            input_seed: None,
+            wrap_selection: false,
+            palette: Palette::new(),
+            tick: 0,
+            last_frame: None,
+        })
     }
 
     pub fn render(&mut self, exp: &Exp) -> Result<()> {
-        let layer = create_render_layer(exp).map_err(RenderError::RenderTreeError)?;
+        let area = self.terminal.size()?;
+        self.render_in(exp, area, |_| {})
+    }
+
+    /// Like `render`, but lays the UI out in `area` rather than the whole
+    /// terminal, then calls `draw_rest` on the same frame so the caller can
+    /// fill the cells around it with their own widgets.
+    pub fn render_in(
+        &mut self,
+        exp: &Exp,
+        area: Rect,
+        draw_rest: impl FnOnce(&mut Frame),
+    ) -> Result<()> {
+        let layer = self.build_layer(exp, area)?;
+        self.last_frame = None;
+        self.draw(&layer, area, draw_rest)
+    }
+
+    /// Draws a frame with `f`, for mixing topogi content with other ratatui
+    /// widgets. Each call draws a whole new frame, so anything from an
+    /// earlier `render` is replaced rather than drawn over; to combine the
+    /// two, draw topogi trees from inside `f` with `draw_layer` or
+    /// `draw_tree`, where later draws cover earlier ones.
+    pub fn draw_with(&mut self, f: impl FnOnce(&mut Frame)) -> Result<()> {
+        self.last_frame = None;
+        self.terminal.draw(f)?;
+        Ok(())
+    }
+
+    /// Renders the `base` layer, then clears a box in the middle of the
+    /// terminal, `width_pct` by `height_pct` percent of its size, and draws the
+    /// `modal` tree in it.
+    pub fn render_modal(
+        &mut self,
+        base: &Exp,
+        modal: &Exp,
+        width_pct: u16,
+        height_pct: u16,
+    ) -> Result<()> {
+        let area = self.terminal.size()?;
+        let dialog = center_rect(
+            area,
+            Constraint::Percentage(width_pct.min(100)),
+            Constraint::Percentage(height_pct.min(100)),
+        );
+
+        let layer = self.build_layer(base, area)?;
+        let ctx = RenderContext {
+            palette: Some(&self.palette),
+            ..RenderContext::new(dialog)
+        };
+        let modal = create_render_tree_ctx(modal, &ctx).map_err(RenderError::RenderTreeError)?;
+        self.last_frame = None;
+        if area.is_empty() {
+            return Ok(());
+        }
+
         self.terminal.draw(|frame| {
-            let area = frame.size();
             render_layer(&layer, frame, area);
+            frame.render_widget(Clear, dialog);
+            render_tree(&modal, frame, dialog);
         })?;
         Ok(())
     }
 
-    pub fn shutdown(&self) -> Result<()> {
-        stdout().execute(LeaveAlternateScreen)?;
-        disable_raw_mode()?;
+    /// Like `render`, but draws a "Terminal too small" message instead of
+    /// the UI while the terminal is smaller than `min_width` x `min_height`.
+    pub fn render_with_min_size(
+        &mut self,
+        exp: &Exp,
+        min_width: u16,
+        min_height: u16,
+    ) -> Result<()> {
+        let size = self.terminal.size()?;
+        if size.width >= min_width && size.height >= min_height {
+            return self.render(exp);
+        }
+
+        self.last_frame = None;
+        self.terminal.draw(|frame| {
+            let area = frame.size();
+            render_too_small(frame, area, min_width, min_height);
+        })?;
         Ok(())
     }
+
+    /// Like `render`, but skips drawing when the tree and terminal size are
+    /// the same as the last `render_if_changed` call. Returns whether a
+    /// redraw happened.
+    pub fn render_if_changed(&mut self, exp: &Exp) -> Result<bool> {
+        let area = self.terminal.size()?;
+        let layer = self.build_layer(exp, area)?;
+        if self
+            .last_frame
+            .as_ref()
+            .is_some_and(|(last_area, last)| *last_area == area && *last == layer)
+        {
+            return Ok(false);
+        }
+
+        self.draw(&layer, area, |_| {})?;
+        self.last_frame = Some((area, layer));
+        Ok(true)
+    }
+
+    fn build_layer(&mut self, exp: &Exp, area: Rect) -> Result<RenderLayer<'static>> {
+        let ctx = RenderContext {
+            palette: Some(&self.palette),
+            ..RenderContext::new(area)
+        };
+        let mut layer = match &mut self.cache {
+            Some(cache) => cache.get_or_build(exp, area, || create_render_layer_ctx(exp, &ctx)),
+            None => create_render_layer_ctx(exp, &ctx),
+        }
+        .map_err(RenderError::RenderTreeError)?;
+        self.apply_state(&mut layer)?;
+        Ok(layer)
+    }
+
+    /// Copies the engine's selection, input, focus and scroll offset into
+    /// `layer`, advances the blink tick, and validates the layer if enabled.
+    fn apply_state(&mut self, layer: &mut RenderLayer) -> Result<()> {
+        self.apply_selection(layer);
+        self.apply_input(layer);
+        let (dy, dx) = self.scroll;
+        let tick = self.tick;
+        self.tick = self.tick.wrapping_add(1);
+        for tree in layer.iter_mut() {
+            apply_focus(tree, self.focus.as_deref());
+            apply_blink(tree, tick);
+            tree.walk_mut(&mut |node| {
+                if let RenderTree::Paragraph { scroll, .. } = node {
+                    *scroll = (scroll.0.saturating_add(dy), scroll.1.saturating_add(dx));
+                }
+            });
+        }
+        if self.validate_constraints {
+            layer
+                .iter()
+                .try_for_each(validate_constraints)
+                .map_err(RenderError::RenderTreeError)?;
+        }
+        Ok(())
+    }
+
+    fn draw(
+        &mut self,
+        layer: &RenderLayer,
+        area: Rect,
+        draw_rest: impl FnOnce(&mut Frame),
+    ) -> Result<()> {
+        let area = area.intersection(self.terminal.size()?);
+        let track_overflow = self.track_overflow && !area.is_empty();
+        let mut overflows = Vec::new();
+        self.terminal.draw(|frame| {
+            if track_overflow {
+                overflows = layer_overflows(layer, area);
+            }
+            render_layer(layer, frame, area);
+            draw_rest(frame);
+        })?;
+        self.overflows = overflows;
+        Ok(())
+    }
+
+    /// Copies the engine's selection into the layer's `(focused)` list,
+    /// clamping it to the list's current length.
+    fn apply_selection(&mut self, layer: &mut RenderLayer) {
+        self.selection_len = 0;
+        if let Some((items, selected)) = layer.iter_mut().find_map(focused_list) {
+            self.selection_len = items.len();
+            self.selection = self
+                .selection
+                .filter(|_| !items.is_empty())
+                .map(|i| i.min(items.len() - 1));
+            *selected = self.selection;
+        }
+    }
+
+    /// Copies the engine's input state into the layer's first input field.
+    /// The state is seeded from the field on the first render, and again
+    /// whenever the field's `(value ...)` or `(cursor ...)` changes.
+    fn apply_input(&mut self, layer: &mut RenderLayer) {
+        if let Some((value, cursor, active)) = layer.iter_mut().find_map(input_field) {
+            *active = true;
+            let seed = (value.clone(), *cursor);
+            let reseed =
+                self.input.is_none() || self.input_seed.as_ref().is_some_and(|last| *last != seed);
+            if reseed {
+                self.input = Some(InputState {
+                    value: seed.0.clone(),
+                    cursor: seed.1,
+                });
+            }
+            self.input_seed = Some(seed);
+
+            let state = self.input.as_ref().expect("input state was just seeded");
+            value.clone_from(&state.value);
+            *cursor = state.cursor;
+        }
+    }
+
+    /// The input field's current text, once it has been rendered or edited.
+    pub fn input_value(&self) -> Option<&str> {
+        self.input.as_ref().map(|state| state.value.as_str())
+    }
+
+    /// Replaces the input field's text, with the cursor at its end. Takes
+    /// effect on the next render.
+    pub fn set_input(&mut self, value: &str) {
+        self.input = Some(InputState {
+            value: value.to_string(),
+            cursor: value.chars().count(),
+        });
+    }
+
+    /// Discards edits to the input field, so the next render shows its
+    /// `(value ...)` again.
+    pub fn clear_input(&mut self) {
+        self.input = None;
+    }
+
+    /// Inserts `c` at the input field's cursor. Takes effect on the next
+    /// render.
+    pub fn insert_char(&mut self, c: char) {
+        self.input
+            .get_or_insert_with(InputState::default)
+            .insert_char(c);
+    }
+
+    /// Deletes the character before the input field's cursor.
+    pub fn backspace(&mut self) {
+        self.input
+            .get_or_insert_with(InputState::default)
+            .backspace();
+    }
+
+    /// Moves the input field's cursor `delta` characters.
+    pub fn move_cursor(&mut self, delta: isize) {
+        self.input
+            .get_or_insert_with(InputState::default)
+            .move_cursor(delta);
+    }
+
+    /// The selected index of the `(focused)` list, as of the last render.
+    pub fn selected(&self) -> Option<usize> {
+        self.selection
+    }
+
+    /// Moves the focused list's selection down one item. Takes effect on the
+    /// next render.
+    pub fn select_next(&mut self) {
+        self.selection = next_selection(self.selection, self.selection_len, self.wrap_selection);
+    }
+
+    /// Moves the focused list's selection up one item.
+    pub fn select_prev(&mut self) {
+        self.selection = prev_selection(self.selection, self.selection_len, self.wrap_selection);
+    }
+
+    /// Gives focus to the block declared with `(id name)`, drawing its border
+    /// highlighted. Takes effect on the next render.
+    pub fn set_focus(&mut self, id: impl Into<String>) {
+        self.focus = Some(id.into());
+    }
+
+    /// The id of the focused block, if any.
+    pub fn focus(&self) -> Option<&str> {
+        self.focus.as_deref()
+    }
+
+    /// Makes selection wrap around at the ends of the list. Off by default.
+    pub fn set_selection_wrap(&mut self, enabled: bool) {
+        self.wrap_selection = enabled;
+    }
+
+    /// Registers named colors that `(fg name)`-style clauses resolve before
+    /// falling back to ANSI names and hex codes.
+    pub fn set_palette(&mut self, palette: HashMap<String, Color>) {
+        self.palette = palette;
+        self.last_frame = None;
+        if let Some(cache) = &mut self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Reuses the tree built for an identical expression and terminal size
+    /// instead of parsing it again. Off by default.
+    pub fn set_render_cache(&mut self, enabled: bool) {
+        self.cache = enabled.then(RenderCache::new);
+    }
+
+    pub fn render_cache(&self) -> Option<&RenderCache> {
+        self.cache.as_ref()
+    }
+
+    /// The `(y, x)` offset added to every paragraph's `(scroll y x)` clause
+    /// when rendering.
+    pub fn scroll_offset(&self) -> (u16, u16) {
+        self.scroll
+    }
+
+    /// Moves the scroll offset by `dy` rows and `dx` columns, never going
+    /// below zero.
+    pub fn scroll_by(&mut self, dy: i32, dx: i32) {
+        let (y, x) = self.scroll;
+        self.scroll = (
+            (i32::from(y) + dy).clamp(0, i32::from(u16::MAX)) as u16,
+            (i32::from(x) + dx).clamp(0, i32::from(u16::MAX)) as u16,
+        );
+    }
+
+    /// Makes `render` reject stacks whose percentage constraints sum past
+    /// 100 instead of letting ratatui clip them. Off by default.
+    pub fn set_constraint_validation(&mut self, enabled: bool) {
+        self.validate_constraints = enabled;
+    }
+
+    /// Loads `path`, resolving `(include "file")` forms relative to each
+    /// including file, and renders the result.
+    pub fn render_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let exp = include::load_file(path.as_ref()).map_err(RenderError::RenderTreeError)?;
+        self.render(&exp)
+    }
+
+    /// Enables recording of nodes whose content is clipped by their area.
+    /// Off by default since it walks the tree a second time per frame.
+    pub fn set_overflow_tracking(&mut self, enabled: bool) {
+        self.track_overflow = enabled;
+        self.overflows.clear();
+    }
+
+    pub fn last_overflows(&self) -> &[OverflowReport] {
+        &self.overflows
+    }
+
+    /// Renders `header` and `footer` bars around `body`, each built for the
+    /// region it is drawn in and with the engine's palette, selection, input
+    /// and focus, as `render` does.
+    pub fn render_chrome(
+        &mut self,
+        header: Option<&Exp>,
+        body: &Exp,
+        footer: Option<&Exp>,
+    ) -> Result<()> {
+        let area = self.terminal.size()?;
+        let (header_area, body_area, footer_area) =
+            chrome_areas(header.is_some(), footer.is_some(), area);
+        let regions = [
+            header.zip(header_area),
+            Some((body, body_area)),
+            footer.zip(footer_area),
+        ];
+
+        let mut layer = regions
+            .iter()
+            .flatten()
+            .map(|&(exp, area)| {
+                let ctx = RenderContext {
+                    palette: Some(&self.palette),
+                    ..RenderContext::new(area)
+                };
+                create_render_tree_ctx(&expand_repeats(exp, ctx.max_depth)?, &ctx)
+            })
+            .collect::<std::result::Result<RenderLayer, _>>()
+            .map_err(RenderError::RenderTreeError)?;
+        self.apply_state(&mut layer)?;
+        self.last_frame = None;
+        if area.is_empty() {
+            return Ok(());
+        }
+
+        self.terminal.draw(|frame| {
+            for (tree, &(_, area)) in layer.iter().zip(regions.iter().flatten()) {
+                render_tree(tree, frame, area);
+            }
+        })?;
+        Ok(())
+    }
+
+    /// Waits up to `timeout` for a key, mouse or resize event. Resize events
+    /// are passed to `on_resize` before being returned.
+    pub fn poll_event(&mut self, timeout: Duration) -> Result<Option<Event>> {
+        if !event::poll(timeout)? {
+            return Ok(None);
+        }
+
+        let event = event::read()?;
+        if let Event::Resize(width, height) = event {
+            self.on_resize(width, height)?;
+        }
+        Ok(Some(event))
+    }
+
+    /// Resizes the terminal's buffers to `width` x `height`. Trees are laid
+    /// out against the frame size on every render, so calling `render` again
+    /// after this is enough to redraw for the new size.
+    pub fn on_resize(&mut self, width: u16, height: u16) -> Result<()> {
+        self.terminal.resize(Rect::new(0, 0, width, height))?;
+        self.last_frame = None;
+        Ok(())
+    }
+}
+
+/// Draws a render layer into `area` of an existing frame, so topogi content
+/// can be composed inside any `terminal.draw(|frame| ...)` closure.
+pub fn draw_layer(layer: &RenderLayer, frame: &mut Frame, area: Rect) {
+    render_layer(layer, frame, area);
+}
+
+/// Draws a single render tree into `area` of an existing frame.
+pub fn draw_tree(tree: &RenderTree, frame: &mut Frame, area: Rect) {
+    render_tree(tree, frame, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_render_to_buffer() {
+        let exp = parse(r#"(layer (block "title" "content" (style (border all))))"#);
+        assert_eq!(
+            UIEngine::render_to_buffer(&exp, 11, 3).unwrap(),
+            Buffer::with_lines(["┌title────┐", "│content  │", "└─────────┘"])
+        );
+    }
+
+    #[test]
+    fn test_render_to_lines() {
+        let exp = parse(
+            r#"(layer
+                 (stack vertical
+                   ((length 3) (block "title" "content" (style (border all))))
+                   ((length 1) "x")))"#,
+        );
+        assert_eq!(
+            UIEngine::render_to_lines(&exp, 11, 5).unwrap(),
+            vec!["┌title────┐", "│content  │", "└─────────┘", "x", ""]
+        );
+    }
+
+    #[test]
+    fn test_draw_with() {
+        let layer = render_tree::create_render_layer(&parse(r#"(layer "topogi")"#)).unwrap();
+        let mut ui = UIEngine::with_backend(TestBackend::new(6, 2)).unwrap();
+        ui.draw_with(|frame| {
+            draw_layer(&layer, frame, Rect::new(0, 0, 6, 1));
+            frame.render_widget(
+                ratatui::widgets::Paragraph::new("widget"),
+                Rect::new(0, 1, 6, 1),
+            );
+        })
+        .unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["topogi", "widget"]));
+    }
+
+    #[test]
+    fn test_list_selection() {
+        let exp = parse(r#"(layer (list (focused) (highlight ">") "a" "b"))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(3, 2)).unwrap();
+        ui.render(&exp).unwrap();
+        ui.select_next();
+        ui.select_next();
+        ui.render(&exp).unwrap();
+        assert_eq!(ui.selected(), Some(1));
+        let buffer = ui.terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 1).symbol(), ">");
+        assert_eq!(buffer.get(1, 1).symbol(), "b");
+
+        ui.select_next();
+        assert_eq!(ui.selected(), Some(1));
+        ui.set_selection_wrap(true);
+        ui.select_next();
+        assert_eq!(ui.selected(), Some(0));
+    }
+
+    #[test]
+    fn test_render_if_changed() {
+        let mut ui = UIEngine::with_backend(TestBackend::new(5, 1)).unwrap();
+        assert!(ui.render_if_changed(&parse(r#"(layer "a")"#)).unwrap());
+        assert!(!ui.render_if_changed(&parse(r#"(layer "a")"#)).unwrap());
+        assert!(ui.render_if_changed(&parse(r#"(layer "b")"#)).unwrap());
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["b    "]));
+    }
+
+    #[test]
+    fn test_render_chrome_uses_engine_state() {
+        let mut ui = UIEngine::with_backend(TestBackend::new(6, 5)).unwrap();
+        ui.set_palette(HashMap::from([("accent".to_string(), Color::Magenta)]));
+        ui.set_focus("body");
+        ui.render_chrome(
+            Some(&parse(
+                r#"(block "" "" (style (border all) (border-color accent)))"#,
+            )),
+            &parse(r#"(block "" "" (style (border all)) (id body))"#),
+            None,
+        )
+        .unwrap();
+
+        let buffer = ui.terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).fg, Color::Magenta);
+        assert_eq!(buffer.get(0, 3).symbol(), "┌");
+        assert_eq!(buffer.get(0, 3).fg, Color::Yellow);
+    }
+
+    #[test]
+    fn test_scroll_by_shifts_paragraphs() {
+        let exp = parse(r#"(layer (paragraph "one two three" (wrap true)))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(5, 2)).unwrap();
+        ui.render(&exp).unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["one  ", "two  "]));
+
+        ui.scroll_by(1, 0);
+        ui.render(&exp).unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["two  ", "three"]));
+
+        ui.scroll_by(-5, 0);
+        ui.render(&exp).unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["one  ", "two  "]));
+    }
+
+    #[test]
+    fn test_render_if_changed_after_chrome() {
+        let exp = parse(r#"(layer "a")"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(5, 1)).unwrap();
+        assert!(ui.render_if_changed(&exp).unwrap());
+        ui.render_chrome(None, &parse(r#""chrome""#), None).unwrap();
+        assert!(ui.render_if_changed(&exp).unwrap());
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["a    "]));
+    }
+
+    #[test]
+    fn test_render_with_min_size() {
+        let exp = parse(r#"(layer "content")"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(10, 3)).unwrap();
+        ui.render_with_min_size(&exp, 20, 5).unwrap();
+
+        let buffer = ui.terminal.backend().buffer();
+        let rows = (0..3)
+            .map(|y| {
+                (0..10)
+                    .map(|x| buffer.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert!(rows[0].contains("Terminal"));
+        assert!(rows[1].contains("too small"));
+
+        ui.render_with_min_size(&exp, 10, 3).unwrap();
+        assert_eq!(ui.terminal.backend().buffer().get(0, 0).symbol(), "c");
+    }
+
+    #[test]
+    fn test_resize() {
+        let exp = parse(r#"(layer (block "t" "x" (style (border all))))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(5, 3)).unwrap();
+        ui.render(&exp).unwrap();
+
+        ui.terminal.backend_mut().resize(8, 4);
+        ui.on_resize(8, 4).unwrap();
+        ui.render(&exp).unwrap();
+        ui.terminal.backend().assert_buffer(&Buffer::with_lines([
+            "┌t─────┐",
+            "│x     │",
+            "│      │",
+            "└──────┘",
+        ]));
+    }
+
+    #[test]
+    fn test_with_backend() {
+        let exp = parse(r#"(layer (block "title" "content" (style (border all))))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(11, 3)).unwrap();
+        ui.render(&exp).unwrap();
+        ui.terminal.backend().assert_buffer(&Buffer::with_lines([
+            "┌title────┐",
+            "│content  │",
+            "└─────────┘",
+        ]));
+    }
+
+    #[test]
+    fn test_palette() {
+        let exp = parse(r#"(layer (text "hi" (style (fg accent))))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(2, 1)).unwrap();
+        assert!(ui.render(&exp).is_err());
+
+        ui.set_palette(HashMap::from([(
+            "accent".to_string(),
+            Color::Rgb(0xff, 0x80, 0x00),
+        )]));
+        ui.render(&exp).unwrap();
+        let buffer = ui.terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).symbol(), "h");
+        assert_eq!(buffer.get(0, 0).fg, Color::Rgb(0xff, 0x80, 0x00));
+    }
+
+    #[test]
+    fn test_render_in() {
+        let exp = parse(r#"(layer (block "abc" (style (border none))))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(6, 3)).unwrap();
+        ui.render_in(&exp, Rect::new(2, 1, 3, 1), |_| {}).unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["      ", "  abc ", "      "]));
+    }
+
+    #[test]
+    fn test_render_in_beside_own_widgets() {
+        let exp = parse(r#"(layer (block "side" (style (border none))))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(10, 1)).unwrap();
+        ui.render_in(&exp, Rect::new(0, 0, 4, 1), |frame| {
+            frame.render_widget(
+                ratatui::widgets::Paragraph::new("main"),
+                Rect::new(5, 0, 5, 1),
+            );
+        })
+        .unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["side main "]));
+    }
+
+    #[test]
+    fn test_render_cache() {
+        let exp = parse(r#"(layer (block "title" "content"))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(10, 3)).unwrap();
+        ui.set_render_cache(true);
+        ui.render(&exp).unwrap();
+        ui.render(&exp).unwrap();
+
+        let cache = ui.render_cache().unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+    }
+
+    #[test]
+    fn test_render_modal() {
+        let base =
+            parse(r#"(layer (text "xxxxxxxxxx\nxxxxxxxxxx\nxxxxxxxxxx\nxxxxxxxxxx\nxxxxxxxxxx"))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(10, 5)).unwrap();
+        ui.render_modal(&base, &parse(r#""ok""#), 40, 20).unwrap();
+        ui.terminal.backend().assert_buffer(&Buffer::with_lines([
+            "xxxxxxxxxx",
+            "xxxxxxxxxx",
+            "xxxok  xxx",
+            "xxxxxxxxxx",
+            "xxxxxxxxxx",
+        ]));
+    }
+
+    #[test]
+    fn test_input_editing() {
+        let exp = parse(r#"(layer (input (value "helo") (cursor 3)))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(7, 1)).unwrap();
+        ui.render(&exp).unwrap();
+        ui.insert_char('l');
+        ui.move_cursor(10);
+        ui.insert_char('!');
+        ui.render(&exp).unwrap();
+
+        assert_eq!(ui.input_value(), Some("hello!"));
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["hello! "]));
+
+        ui.backspace();
+        ui.render(&exp).unwrap();
+        assert_eq!(ui.input_value(), Some("hello"));
+    }
+
+    #[test]
+    fn test_input_reseeds() {
+        let mut ui = UIEngine::with_backend(TestBackend::new(7, 1)).unwrap();
+        ui.render(&parse(r#"(layer (input (value "one")))"#))
+            .unwrap();
+        ui.insert_char('!');
+        ui.render(&parse(r#"(layer (input (value "one")))"#))
+            .unwrap();
+        assert_eq!(ui.input_value(), Some("one!"));
+
+        ui.render(&parse(r#"(layer (input (value "two")))"#))
+            .unwrap();
+        assert_eq!(ui.input_value(), Some("two"));
+
+        ui.set_input("three");
+        ui.render(&parse(r#"(layer (input (value "two")))"#))
+            .unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["three  "]));
+
+        ui.clear_input();
+        ui.render(&parse(r#"(layer (input (value "two")))"#))
+            .unwrap();
+        assert_eq!(ui.input_value(), Some("two"));
+    }
+
+    #[test]
+    fn test_software_blink() {
+        let exp = parse(r#"(layer (text "ALERT" (blink)))"#);
+        let mut ui = UIEngine::with_backend(TestBackend::new(5, 1)).unwrap();
+        for _ in 0..blink::BLINK_FRAMES {
+            ui.render(&exp).unwrap();
+            ui.terminal
+                .backend()
+                .assert_buffer(&Buffer::with_lines(["ALERT"]));
+        }
+        ui.render(&exp).unwrap();
+        ui.terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["     "]));
+    }
+
+    #[test]
+    fn test_cursor_follows_active_input() {
+        let exp = parse(
+            r#"(layer
+                 (stack vertical
+                   ((length 1) (input (value "first") (cursor 2)))
+                   ((length 1) (input (value "second") (cursor 4)))))"#,
+        );
+        let mut ui = UIEngine::with_backend(TestBackend::new(8, 2)).unwrap();
+        ui.render(&exp).unwrap();
+        assert_eq!(ui.terminal.get_cursor().unwrap(), (2, 0));
+    }
 }
@@ -0,0 +1,283 @@
+use ratatui::style::Color;
+use topogi_lang::ast::Exp;
+
+use crate::{
+    render_tree::{
+        check_symbol, create_list_with_len, create_list_with_minlen, RenderContext, RenderTree,
+        Result,
+    },
+    style::{parse_color, Palette},
+};
+
+fn create_highlight(exp: &Exp) -> Result<String> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "highlight")?;
+
+    Ok(elems[1].to_string())
+}
+
+fn create_focused(exp: &Exp) -> Result<()> {
+    let elems = create_list_with_len(exp, 1)?;
+    check_symbol(&elems[0], "focused")
+}
+
+fn create_show_count(exp: &Exp) -> Result<()> {
+    let elems = create_list_with_len(exp, 1)?;
+    check_symbol(&elems[0], "show-count")
+}
+
+pub fn is_zebra(exp: &Exp) -> bool {
+    matches!(
+        exp.as_list()
+            .and_then(|elems| elems.first())
+            .and_then(|e| e.as_symbol()),
+        Some("zebra")
+    )
+}
+
+/// `(zebra (bg color))`, the background of every other row of a list or
+/// table, starting with the second.
+pub fn create_zebra(exp: &Exp, palette: Option<&Palette>) -> Result<Color> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "zebra")?;
+
+    let bg = create_list_with_len(&elems[1], 2)?;
+    check_symbol(&bg[0], "bg")?;
+    parse_color(&bg[1], palette)
+}
+
+pub fn create_list(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    check_symbol(&elems[0], "list")?;
+
+    let mut items = &elems[1..];
+    let mut highlight_symbol = None;
+    let mut focused = false;
+    let mut zebra = None;
+    let mut show_count = false;
+    while let Some(first) = items.first() {
+        if let Ok(symbol) = create_highlight(first) {
+            highlight_symbol = Some(symbol);
+        } else if create_focused(first).is_ok() {
+            focused = true;
+        } else if create_show_count(first).is_ok() {
+            show_count = true;
+        } else if is_zebra(first) {
+            zebra = Some(create_zebra(first, ctx.palette)?);
+        } else {
+            break;
+        }
+        items = &items[1..];
+    }
+
+    Ok(RenderTree::List {
+        items: items.iter().map(|e| e.to_string()).collect(),
+        highlight_symbol,
+        focused,
+        selected: None,
+        zebra,
+        show_count,
+    })
+}
+
+/// Finds the first list marked `(focused)` in `tree`, returning its items and
+/// its selection so the engine can fill in the selected index.
+pub fn focused_list<'t>(
+    tree: &'t mut RenderTree<'_>,
+) -> Option<(&'t [String], &'t mut Option<usize>)> {
+    match tree {
+        RenderTree::List {
+            items,
+            focused: true,
+            selected,
+            ..
+        } => Some((items.as_slice(), selected)),
+        RenderTree::Block(_, content)
+        | RenderTree::Float { content, .. }
+        | RenderTree::Aligned { content, .. }
+        | RenderTree::Focusable { content, .. }
+        | RenderTree::Blink { content, .. } => focused_list(content),
+        RenderTree::Stack(_, _, stack_elems) => stack_elems
+            .iter_mut()
+            .find_map(|e| focused_list(&mut e.content)),
+        RenderTree::Responsive(_, content, otherwise) => {
+            focused_list(content).or_else(|| otherwise.as_mut().and_then(|o| focused_list(o)))
+        }
+        _ => None,
+    }
+}
+
+/// Moves `selected` one item forward in a list of `len` items.
+pub fn next_selection(selected: Option<usize>, len: usize, wrap: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    match selected {
+        None => Some(0),
+        Some(i) if i + 1 < len => Some(i + 1),
+        Some(_) if wrap => Some(0),
+        Some(_) => Some(len - 1),
+    }
+}
+
+/// Moves `selected` one item back in a list of `len` items.
+pub fn prev_selection(selected: Option<usize>, len: usize, wrap: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    match selected {
+        None => Some(len - 1),
+        Some(0) if wrap => Some(len - 1),
+        Some(0) => Some(0),
+        Some(i) => Some(i.min(len) - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_tree;
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_list() {
+        let exp = parse(r#"(list "item1" "item2")"#);
+        assert_eq!(
+            create_list(&exp, &RenderContext::default()),
+            Ok(RenderTree::List {
+                items: vec!["item1".to_string(), "item2".to_string()],
+                highlight_symbol: None,
+                focused: false,
+                selected: None,
+                zebra: None,
+                show_count: false,
+            })
+        );
+
+        let exp = parse(r#"(list (highlight "> ") "a" "b")"#);
+        assert_eq!(
+            create_list(&exp, &RenderContext::default()),
+            Ok(RenderTree::List {
+                items: vec!["a".to_string(), "b".to_string()],
+                highlight_symbol: Some("> ".to_string()),
+                focused: false,
+                selected: None,
+                zebra: None,
+                show_count: false,
+            })
+        );
+
+        let exp = parse(r#"(list (focused) (highlight "> ") "a")"#);
+        assert_eq!(
+            create_list(&exp, &RenderContext::default()),
+            Ok(RenderTree::List {
+                items: vec!["a".to_string()],
+                highlight_symbol: Some("> ".to_string()),
+                focused: true,
+                selected: None,
+                zebra: None,
+                show_count: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_selection() {
+        assert_eq!(next_selection(None, 3, false), Some(0));
+        assert_eq!(next_selection(Some(1), 3, false), Some(2));
+        assert_eq!(next_selection(Some(2), 3, false), Some(2));
+        assert_eq!(next_selection(Some(2), 3, true), Some(0));
+        assert_eq!(prev_selection(Some(0), 3, false), Some(0));
+        assert_eq!(prev_selection(Some(0), 3, true), Some(2));
+        assert_eq!(prev_selection(Some(2), 3, true), Some(1));
+        assert_eq!(next_selection(None, 0, true), None);
+    }
+
+    #[test]
+    fn test_render_selected_item() {
+        let mut tree = create_list(
+            &parse(r#"(list (focused) (highlight ">") "a" "b")"#),
+            &RenderContext::default(),
+        )
+        .unwrap();
+        let (_, selected) = focused_list(&mut tree).unwrap();
+        *selected = Some(1);
+
+        let mut terminal = Terminal::new(TestBackend::new(3, 2)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).symbol(), " ");
+        assert_eq!(buffer.get(1, 0).symbol(), "a");
+        assert_eq!(buffer.get(0, 1).symbol(), ">");
+        assert_eq!(buffer.get(1, 1).symbol(), "b");
+    }
+
+    #[test]
+    fn test_render_empty_list() {
+        let tree = create_list(&parse("(list)"), &RenderContext::default()).unwrap();
+        assert_eq!(
+            tree,
+            RenderTree::List {
+                items: vec![],
+                highlight_symbol: None,
+                focused: false,
+                selected: None,
+                zebra: None,
+                show_count: false,
+            }
+        );
+
+        let mut terminal = Terminal::new(TestBackend::new(4, 2)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["    ", "    "]));
+    }
+
+    #[test]
+    fn test_show_count() {
+        let mut tree = create_list(
+            &parse(r#"(list (focused) (show-count) "1" "2" "3" "4" "5" "6" "7" "8" "9" "10")"#),
+            &RenderContext::default(),
+        )
+        .unwrap();
+        let (_, selected) = focused_list(&mut tree).unwrap();
+        *selected = Some(5);
+
+        let mut terminal = Terminal::new(TestBackend::new(17, 4)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let row = |y| {
+            (0..17)
+                .map(|x| buffer.get(x, y).symbol())
+                .collect::<String>()
+        };
+        assert_eq!(row(0).trim_end(), "4");
+        assert_eq!(row(2).trim_end(), "6");
+        assert_eq!(row(3), "showing 4-6 of 10");
+        assert!(buffer
+            .get(0, 3)
+            .modifier
+            .contains(ratatui::style::Modifier::DIM));
+    }
+}
@@ -0,0 +1,88 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_list_with_len, create_list_with_minlen, RenderTree, RenderTreeError,
+    Result,
+};
+
+fn create_follow(exp: &Exp) -> Result<bool> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "follow")?;
+
+    match elems[1].as_symbol() {
+        Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        _ => Err(RenderTreeError::ExpectedSymbol("true | false", exp.into())),
+    }
+}
+
+/// `(log [(follow true)] line*)`, a wrapped list of lines that stays scrolled
+/// to the newest ones while following.
+pub fn create_log(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    check_symbol(&elems[0], "log")?;
+
+    let mut lines = &elems[1..];
+    let mut follow = false;
+    if let Some(Ok(enabled)) = lines.first().map(create_follow) {
+        follow = enabled;
+        lines = &lines[1..];
+    }
+
+    Ok(RenderTree::Log {
+        lines: lines.iter().map(|e| e.to_string()).collect(),
+        follow,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+    use ratatui::buffer::Buffer;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_log() {
+        let exp = parse(r#"(log (follow true) "line1" "line2")"#);
+        assert_eq!(
+            create_log(&exp),
+            Ok(RenderTree::Log {
+                lines: vec!["line1".to_string(), "line2".to_string()],
+                follow: true,
+            })
+        );
+
+        let exp = parse(r#"(log "line1")"#);
+        assert_eq!(
+            create_log(&exp),
+            Ok(RenderTree::Log {
+                lines: vec!["line1".to_string()],
+                follow: false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_log_follows_newest_lines() {
+        let lines = (0..50)
+            .map(|i| format!("\"line{}\"", i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let exp = parse(&format!("(layer (log (follow true) {}))", lines));
+        assert_eq!(
+            UIEngine::render_to_buffer(&exp, 6, 5).unwrap(),
+            Buffer::with_lines(["line45", "line46", "line47", "line48", "line49"])
+        );
+
+        let exp = parse(&format!("(layer (log {}))", lines));
+        assert_eq!(
+            UIEngine::render_to_buffer(&exp, 6, 5).unwrap(),
+            Buffer::with_lines(["line0 ", "line1 ", "line2 ", "line3 ", "line4 "])
+        );
+    }
+}
@@ -0,0 +1,80 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_integer, create_list_with_len, create_list_with_minlen, RenderTree, Result,
+};
+
+const SEPARATOR: &str = " ";
+
+pub fn create_marquee(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "marquee")?;
+
+    let text = elems[1].to_string();
+    let offset = match elems.get(2) {
+        Some(offset) => create_offset(offset)?,
+        None => 0,
+    };
+
+    Ok(RenderTree::Marquee { text, offset })
+}
+
+fn create_offset(exp: &Exp) -> Result<usize> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "offset")?;
+
+    Ok(create_integer(&elems[1])?.max(0) as usize)
+}
+
+pub fn marquee_window(text: &str, offset: usize, width: usize) -> String {
+    let len = text.chars().count();
+    if len <= width {
+        return text.to_string();
+    }
+
+    text.chars()
+        .chain(SEPARATOR.chars())
+        .cycle()
+        .skip(offset % (len + SEPARATOR.len()))
+        .take(width)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_marquee() {
+        let exp = parse(r#"(marquee "breaking news" (offset 3))"#);
+        assert_eq!(
+            create_marquee(&exp),
+            Ok(RenderTree::Marquee {
+                text: "breaking news".to_string(),
+                offset: 3
+            })
+        );
+
+        let exp = parse(r#"(marquee "news")"#);
+        assert_eq!(
+            create_marquee(&exp),
+            Ok(RenderTree::Marquee {
+                text: "news".to_string(),
+                offset: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_marquee_window() {
+        assert_eq!(marquee_window("hello world", 0, 5), "hello");
+        assert_eq!(marquee_window("hello world", 6, 5), "world");
+        assert_eq!(marquee_window("hello world", 9, 5), "ld he");
+        assert_eq!(marquee_window("hello world", 12, 5), "hello");
+        assert_eq!(marquee_window("short", 4, 10), "short");
+    }
+}
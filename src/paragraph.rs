@@ -0,0 +1,289 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_integer, create_list_with_len, create_list_with_minlen, ErrorContext,
+    RenderTree, RenderTreeError, Result,
+};
+
+fn create_bool(exp: &Exp) -> Result<bool> {
+    match exp.as_symbol() {
+        Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        _ => Err(RenderTreeError::ExpectedSymbol("true | false", exp.into())),
+    }
+}
+
+fn create_wrap_option(exp: &Exp, name: &'static str) -> Result<bool> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], name)?;
+
+    create_bool(&elems[1])
+}
+
+/// `(wrap true|false)`, `(wrap word)` for `(wrap true)`, or
+/// `(wrap (enabled b) (trim b))` to keep leading whitespace on wrapped lines.
+/// Returns whether to wrap and whether to trim; both default to true in the
+/// long form.
+fn create_wrap(exp: &Exp) -> Result<(bool, bool)> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "wrap")?;
+
+    if elems.len() == 2 && elems[1].as_symbol() == Some("word") {
+        return Ok((true, true));
+    }
+    if elems.len() == 2 && elems[1].as_list().is_none() {
+        return Ok((create_bool(&elems[1])?, true));
+    }
+
+    let (mut enabled, mut trim) = (true, true);
+    for option in &elems[1..] {
+        if let Ok(value) = create_wrap_option(option, "enabled") {
+            enabled = value;
+        } else {
+            trim = create_wrap_option(option, "trim")?;
+        }
+    }
+    Ok((enabled, trim))
+}
+
+fn create_scroll(exp: &Exp) -> Result<(u16, u16)> {
+    let elems = create_list_with_len(exp, 3)?;
+    check_symbol(&elems[0], "scroll")?;
+
+    let y = create_integer(&elems[1])?.max(0) as u16;
+    let x = create_integer(&elems[2])?.max(0) as u16;
+
+    Ok((y, x))
+}
+
+/// `(hanging-indent n)`, the spaces wrapped continuation lines start with.
+fn create_hanging_indent(exp: &Exp) -> Result<u16> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "hanging-indent")?;
+
+    u16::try_from(create_integer(&elems[1])?)
+        .map_err(|_| RenderTreeError::NegativeValue(ErrorContext::from(&elems[1])))
+}
+
+fn is_hanging_indent(exp: &Exp) -> bool {
+    matches!(
+        exp.as_list()
+            .and_then(|elems| elems.first())
+            .and_then(|e| e.as_symbol()),
+        Some("hanging-indent")
+    )
+}
+
+pub fn create_paragraph(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "paragraph")?;
+
+    let text = elems[1].to_string();
+    let mut wrap = false;
+    let mut trim = true;
+    let mut scroll = (0, 0);
+    let mut hanging_indent = 0;
+    for clause in elems.iter().skip(2) {
+        if let Ok(options) = create_wrap(clause) {
+            (wrap, trim) = options;
+        } else if is_hanging_indent(clause) {
+            hanging_indent = create_hanging_indent(clause)?;
+        } else {
+            scroll = create_scroll(clause)?;
+        }
+    }
+
+    Ok(RenderTree::Paragraph {
+        text,
+        wrap,
+        trim,
+        scroll,
+        hanging_indent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_tree;
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_paragraph() {
+        let exp = parse(r#"(paragraph "long text")"#);
+        assert_eq!(
+            create_paragraph(&exp),
+            Ok(RenderTree::Paragraph {
+                text: "long text".to_string(),
+                wrap: false,
+                trim: true,
+                scroll: (0, 0),
+                hanging_indent: 0,
+            })
+        );
+
+        let exp = parse(r#"(paragraph "long text" (wrap true))"#);
+        assert_eq!(
+            create_paragraph(&exp),
+            Ok(RenderTree::Paragraph {
+                text: "long text".to_string(),
+                wrap: true,
+                trim: true,
+                scroll: (0, 0),
+                hanging_indent: 0,
+            })
+        );
+
+        let exp = parse(r#"(paragraph "long text" (wrap false))"#);
+        assert_eq!(
+            create_paragraph(&exp),
+            Ok(RenderTree::Paragraph {
+                text: "long text".to_string(),
+                wrap: false,
+                trim: true,
+                scroll: (0, 0),
+                hanging_indent: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_create_paragraph_scroll() {
+        let exp = parse(r#"(paragraph "long text" (wrap true) (scroll 5 2))"#);
+        assert_eq!(
+            create_paragraph(&exp),
+            Ok(RenderTree::Paragraph {
+                text: "long text".to_string(),
+                wrap: true,
+                trim: true,
+                scroll: (5, 2),
+                hanging_indent: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_paragraph_wrap() {
+        let render = |tree: &RenderTree| {
+            let mut terminal = Terminal::new(TestBackend::new(6, 2)).unwrap();
+            terminal
+                .draw(|frame| {
+                    let area = frame.size();
+                    render_tree(tree, frame, area);
+                })
+                .unwrap();
+            terminal.backend().buffer().clone()
+        };
+
+        let tree = create_paragraph(&parse(r#"(paragraph "hello world" (wrap true))"#)).unwrap();
+        assert_eq!(render(&tree), Buffer::with_lines(["hello ", "world "]));
+
+        let tree = create_paragraph(&parse(r#"(paragraph "hello world")"#)).unwrap();
+        assert_eq!(render(&tree), Buffer::with_lines(["hello ", "      "]));
+    }
+
+    #[test]
+    fn test_render_paragraph_scroll() {
+        let tree = create_paragraph(&parse(
+            r#"(paragraph "one two three" (wrap true) (scroll 1 0))"#,
+        ))
+        .unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(5, 2)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["two  ", "three"]));
+    }
+
+    #[test]
+    fn test_wrap_trim() {
+        let exp = parse(r#"(paragraph "text" (wrap (enabled true) (trim false)))"#);
+        assert!(matches!(
+            create_paragraph(&exp),
+            Ok(RenderTree::Paragraph {
+                wrap: true,
+                trim: false,
+                ..
+            })
+        ));
+
+        let exp = parse(r#"(paragraph "text" (wrap (trim true)))"#);
+        assert!(matches!(
+            create_paragraph(&exp),
+            Ok(RenderTree::Paragraph {
+                wrap: true,
+                trim: true,
+                ..
+            })
+        ));
+
+        let exp = parse(r#"(wrap (trim maybe))"#);
+        assert_eq!(
+            create_wrap(&exp),
+            Err(RenderTreeError::ExpectedSymbol(
+                "true | false",
+                parse("maybe").into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render_wrap_trim() {
+        let render = |source: &str| {
+            let tree = create_paragraph(&parse(source)).unwrap();
+            let mut terminal = Terminal::new(TestBackend::new(5, 1)).unwrap();
+            terminal
+                .draw(|frame| {
+                    let area = frame.size();
+                    render_tree(&tree, frame, area);
+                })
+                .unwrap();
+            terminal.backend().buffer().clone()
+        };
+
+        assert_eq!(
+            render(r#"(paragraph "  ab" (wrap (trim true)))"#),
+            Buffer::with_lines(["ab   "])
+        );
+        assert_eq!(
+            render(r#"(paragraph "  ab" (wrap (trim false)))"#),
+            Buffer::with_lines(["  ab "])
+        );
+    }
+
+    #[test]
+    fn test_hanging_indent() {
+        let tree = create_paragraph(&parse(
+            r#"(paragraph "- buy milk, eggs and bread" (wrap word) (hanging-indent 2))"#,
+        ))
+        .unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(12, 3)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        terminal.backend().assert_buffer(&Buffer::with_lines([
+            "- buy milk, ",
+            "  eggs and  ",
+            "  bread     ",
+        ]));
+
+        let exp = parse(r#"(paragraph "x" (hanging-indent -1))"#);
+        assert_eq!(
+            create_paragraph(&exp),
+            Err(RenderTreeError::NegativeValue(parse("-1").into()))
+        );
+    }
+}
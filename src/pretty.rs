@@ -0,0 +1,178 @@
+use std::fmt::Write;
+
+use ratatui::text::{Line, Text};
+
+use crate::render_tree::RenderTree;
+
+impl<'a> RenderTree<'a> {
+    /// An indented outline of the tree, one node per line, with stack
+    /// constraints on their own line above each child and block titles after
+    /// `Block`.
+    pub fn pretty_print(&self) -> String {
+        let mut out = String::new();
+        write_tree(self, 0, &mut out);
+        out
+    }
+}
+
+fn line_content(line: &Line) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+fn text_content(text: &Text) -> String {
+    text.lines
+        .iter()
+        .map(line_content)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn write_line(depth: usize, line: &str, out: &mut String) {
+    writeln!(out, "{}{}", "  ".repeat(depth), line).unwrap();
+}
+
+fn write_tree(tree: &RenderTree, depth: usize, out: &mut String) {
+    match tree {
+        RenderTree::Text(text) => write_line(depth, &format!("Text {:?}", text_content(text)), out),
+        RenderTree::Block(block, content) => {
+            let mut line = "Block".to_string();
+            for title in &block.titles {
+                write!(line, " {:?}", line_content(&title.content)).unwrap();
+            }
+            write_line(depth, &line, out);
+            write_tree(content, depth + 1, out);
+        }
+        RenderTree::Blink { content, .. } => {
+            write_line(depth, "Blink", out);
+            write_tree(content, depth + 1, out);
+        }
+        RenderTree::Focusable { id, content, .. } => {
+            write_line(depth, &format!("Focusable {:?}", id), out);
+            write_tree(content, depth + 1, out);
+        }
+        RenderTree::Stack(direction, _, stack_elems) => {
+            write_line(depth, &format!("Stack {:?}", direction), out);
+            for elem in stack_elems {
+                write_line(depth + 1, &format!("{:?}", elem.constraint), out);
+                write_tree(&elem.content, depth + 2, out);
+            }
+        }
+        RenderTree::VGauge(gauge) => write_line(depth, &format!("VGauge {}", gauge.ratio), out),
+        RenderTree::Marquee { text, offset } => {
+            write_line(depth, &format!("Marquee {:?} offset {}", text, offset), out)
+        }
+        RenderTree::Log { lines, follow } => write_line(
+            depth,
+            &format!("Log {} lines, follow {}", lines.len(), follow),
+            out,
+        ),
+        RenderTree::Input { value, cursor, .. } => {
+            write_line(depth, &format!("Input {:?} cursor {}", value, cursor), out)
+        }
+        RenderTree::Responsive(breakpoint, content, otherwise) => {
+            write_line(depth, &format!("Responsive {:?}", breakpoint), out);
+            write_tree(content, depth + 1, out);
+            if let Some(otherwise) = otherwise {
+                write_line(depth, "Otherwise", out);
+                write_tree(otherwise, depth + 1, out);
+            }
+        }
+        RenderTree::List { items, .. } => write_line(depth, &format!("List {:?}", items), out),
+        RenderTree::Paragraph { text, .. } => {
+            write_line(depth, &format!("Paragraph {:?}", text), out)
+        }
+        RenderTree::Gauge { ratio, .. } => write_line(depth, &format!("Gauge {}", ratio), out),
+        RenderTree::Float { area, content } => {
+            write_line(depth, &format!("Float {:?}", area), out);
+            write_tree(content, depth + 1, out);
+        }
+        RenderTree::Clear => write_line(depth, "Clear", out),
+        RenderTree::Empty => write_line(depth, "Empty", out),
+        RenderTree::Tabs { titles, selected } => write_line(
+            depth,
+            &format!("Tabs {:?} selected {}", titles, selected),
+            out,
+        ),
+        RenderTree::Table {
+            headers, widths, ..
+        } => write_line(depth, &format!("Table {:?} {:?}", headers, widths), out),
+        RenderTree::Scrollbar {
+            orientation,
+            position,
+            content_length,
+        } => write_line(
+            depth,
+            &format!(
+                "Scrollbar {:?} {}/{}",
+                orientation, position, content_length
+            ),
+            out,
+        ),
+        RenderTree::Sparkline { data, .. } => {
+            write_line(depth, &format!("Sparkline {:?}", data), out)
+        }
+        RenderTree::Chart(datasets) => {
+            let names = datasets.iter().map(|d| &d.name).collect::<Vec<_>>();
+            write_line(depth, &format!("Chart {:?}", names), out)
+        }
+        RenderTree::Canvas { shapes, .. } => {
+            write_line(depth, &format!("Canvas ({} shapes)", shapes.len()), out)
+        }
+        RenderTree::Aligned {
+            horizontal,
+            vertical,
+            content,
+        } => {
+            write_line(
+                depth,
+                &format!("Aligned {:?} {:?}", horizontal, vertical),
+                out,
+            );
+            write_tree(content, depth + 1, out);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render_tree::create_render_tree;
+    use topogi_lang::ast::Exp;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_pretty_print() {
+        let exp = parse(
+            r#"(stack vertical
+                 ((length 3) (block "title" "content"))
+                 ((length 1) (block "untitled"))
+                 ((fill 1) (stack horizontal
+                   ((percentage 50) (list "a" "b"))
+                   ((percentage 50) (gauge 0.5)))))"#,
+        );
+        assert_eq!(
+            create_render_tree(&exp).unwrap().pretty_print(),
+            concat!(
+                "Stack Vertical\n",
+                "  Length(3)\n",
+                "    Block \"title\"\n",
+                "      Text \"content\"\n",
+                "  Length(1)\n",
+                "    Block\n",
+                "      Text \"untitled\"\n",
+                "  Fill(1)\n",
+                "    Stack Horizontal\n",
+                "      Percentage(50)\n",
+                "        List [\"a\", \"b\"]\n",
+                "      Percentage(50)\n",
+                "        Gauge 0.5\n",
+            )
+        );
+    }
+}
@@ -1,29 +1,249 @@
-use ratatui::{layout::Direction, text::Text, widgets::Block};
+use std::collections::HashMap;
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Rect},
+    style::Color,
+    text::Text,
+    widgets::ScrollbarOrientation,
+};
 use topogi_lang::ast::Exp;
 
 use crate::{
-    block::create_block,
-    stack::{create_stack, StackElement},
-    text::create_text,
+    align::VerticalAlignment,
+    block::{create_block, BlockStyle},
+    canvas::{create_canvas, CanvasShape},
+    chart::{create_chart, ChartDataset},
+    clear::create_clear,
+    conditional::create_if,
+    float::create_float_node,
+    gauge::create_gauge,
+    input::create_input,
+    list::create_list,
+    log::create_log,
+    marquee::create_marquee,
+    paragraph::create_paragraph,
+    responsive::{create_responsive, Breakpoint},
+    scrollbar::create_scrollbar,
+    spacer::create_spacer,
+    sparkline::create_sparkline,
+    stack::{create_stack, StackElement, StackOptions},
+    style::Palette,
+    table::create_table,
+    tabs::create_tabs,
+    template::{expand_repeats, MAX_REPEAT},
+    text::{create_columns_at, create_text},
+    vgauge::{create_vgauge, VerticalGauge},
 };
 
 pub type Result<T> = std::result::Result<T, RenderTreeError>;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// Information available while building a tree. `area` is the region the
+/// node being built will be laid out in; `env` holds the variables `(if ...)`
+/// nodes test; `palette` names the colors style clauses may refer to.
+/// Nesting deeper than `max_depth` nodes is rejected so deeply nested input
+/// can't overflow the stack.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RenderContext<'e> {
+    pub area: Rect,
+    pub env: Option<&'e HashMap<String, bool>>,
+    pub palette: Option<&'e Palette>,
+    pub depth: usize,
+    pub max_depth: usize,
+}
+
+impl RenderContext<'_> {
+    pub fn new(area: Rect) -> Self {
+        RenderContext {
+            area,
+            env: None,
+            palette: None,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl Default for RenderContext<'_> {
+    /// A context with an empty area, so building a tree never depends on the
+    /// host terminal. `UIEngine` passes the backend's size with
+    /// `RenderContext::new` instead.
+    fn default() -> Self {
+        RenderContext::new(Rect::default())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RenderTree<'a> {
-    Text(Text<'a>),
-    Block(Block<'a>, Box<RenderTree<'a>>),
-    Stack(Direction, Vec<StackElement<'a>>),
+    Text(#[cfg_attr(feature = "serde", serde(with = "crate::serialize::text"))] Text<'a>),
+    Block(BlockStyle<'a>, Box<RenderTree<'a>>),
+    Stack(
+        #[cfg_attr(feature = "serde", serde(with = "crate::serialize::DirectionDef"))] Direction,
+        StackOptions,
+        Vec<StackElement<'a>>,
+    ),
+    VGauge(VerticalGauge),
+    Marquee {
+        text: String,
+        offset: usize,
+    },
+    /// `active` is set by the engine on the field it edits, the only one
+    /// drawn with a cursor.
+    Input {
+        value: String,
+        cursor: usize,
+        active: bool,
+    },
+    Log {
+        lines: Vec<String>,
+        follow: bool,
+    },
+    Responsive(Breakpoint, Box<RenderTree<'a>>, Option<Box<RenderTree<'a>>>),
+    List {
+        items: Vec<String>,
+        highlight_symbol: Option<String>,
+        focused: bool,
+        selected: Option<usize>,
+        zebra: Option<Color>,
+        show_count: bool,
+    },
+    Paragraph {
+        text: String,
+        wrap: bool,
+        trim: bool,
+        scroll: (u16, u16),
+        /// Spaces that wrapped continuation lines are indented by.
+        hanging_indent: u16,
+    },
+    Gauge {
+        ratio: f64,
+        label: Option<String>,
+    },
+    Float {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serialize::RectDef"))]
+        area: Rect,
+        content: Box<RenderTree<'a>>,
+    },
+    Clear,
+    Empty,
+    Tabs {
+        titles: Vec<String>,
+        selected: usize,
+    },
+    Table {
+        headers: Vec<String>,
+        rows: Vec<Vec<String>>,
+        #[cfg_attr(feature = "serde", serde(with = "crate::serialize::constraints"))]
+        widths: Vec<Constraint>,
+        zebra: Option<Color>,
+    },
+    Scrollbar {
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::serialize::ScrollbarOrientationDef")
+        )]
+        orientation: ScrollbarOrientation,
+        position: usize,
+        content_length: usize,
+    },
+    Sparkline {
+        data: Vec<u64>,
+        max: Option<u64>,
+    },
+    Chart(Vec<ChartDataset>),
+    Canvas {
+        x_bounds: [f64; 2],
+        y_bounds: [f64; 2],
+        shapes: Vec<CanvasShape>,
+    },
+    Aligned {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serialize::AlignmentDef"))]
+        horizontal: Alignment,
+        vertical: VerticalAlignment,
+        content: Box<RenderTree<'a>>,
+    },
+    /// Text with a `(blink)` clause; `visible` is toggled by the engine.
+    Blink {
+        visible: bool,
+        content: Box<RenderTree<'a>>,
+    },
+    /// A block with an `(id ...)`; `focused` is filled in by the engine.
+    Focusable {
+        id: String,
+        focused: bool,
+        content: Box<RenderTree<'a>>,
+    },
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+impl<'a> RenderTree<'a> {
+    /// Calls `f` on this node and then on each of its descendants,
+    /// depth-first.
+    pub fn walk(&self, f: &mut dyn FnMut(&RenderTree<'a>)) {
+        f(self);
+        match self {
+            RenderTree::Block(_, content)
+            | RenderTree::Float { content, .. }
+            | RenderTree::Aligned { content, .. }
+            | RenderTree::Focusable { content, .. }
+            | RenderTree::Blink { content, .. } => content.walk(f),
+            RenderTree::Stack(_, _, stack_elems) => {
+                stack_elems.iter().for_each(|e| e.content.walk(f))
+            }
+            RenderTree::Responsive(_, content, otherwise) => {
+                content.walk(f);
+                if let Some(otherwise) = otherwise {
+                    otherwise.walk(f);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Like `walk`, but `f` may change the nodes. Children are visited after
+    /// `f` has run on their parent, so nodes it puts in place are walked too.
+    pub fn walk_mut(&mut self, f: &mut dyn FnMut(&mut RenderTree<'a>)) {
+        f(self);
+        match self {
+            RenderTree::Block(_, content)
+            | RenderTree::Float { content, .. }
+            | RenderTree::Aligned { content, .. }
+            | RenderTree::Focusable { content, .. }
+            | RenderTree::Blink { content, .. } => content.walk_mut(f),
+            RenderTree::Stack(_, _, stack_elems) => {
+                stack_elems.iter_mut().for_each(|e| e.content.walk_mut(f))
+            }
+            RenderTree::Responsive(_, content, otherwise) => {
+                content.walk_mut(f);
+                if let Some(otherwise) = otherwise {
+                    otherwise.walk_mut(f);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct RenderLayer<'a> {
     trees: Vec<RenderTree<'a>>,
+    scale_to_fit: Option<(u16, u16)>,
 }
 
 impl<'a> RenderLayer<'a> {
     pub fn new() -> Self {
-        RenderLayer { trees: Vec::new() }
+        RenderLayer {
+            trees: Vec::new(),
+            scale_to_fit: None,
+        }
+    }
+
+    /// The design size given by `(scale-to-fit w h)`. When the render area is
+    /// larger, top-level stack percentages grow to fill the extra space while
+    /// `length` children keep their fixed size.
+    pub fn scale_to_fit(&self) -> Option<(u16, u16)> {
+        self.scale_to_fit
     }
 
     pub fn add_layer(&mut self, tree: RenderTree<'a>) {
@@ -33,29 +253,237 @@ impl<'a> RenderLayer<'a> {
     pub fn iter(&self) -> std::slice::Iter<RenderTree> {
         self.trees.iter()
     }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<RenderTree<'a>> {
+        self.trees.iter_mut()
+    }
+
+    pub fn len(&self) -> usize {
+        self.trees.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
 }
 
+impl Default for RenderLayer<'_> {
+    fn default() -> Self {
+        RenderLayer::new()
+    }
+}
+
+impl<'a> FromIterator<RenderTree<'a>> for RenderLayer<'a> {
+    fn from_iter<I: IntoIterator<Item = RenderTree<'a>>>(iter: I) -> Self {
+        RenderLayer {
+            trees: iter.into_iter().collect(),
+            scale_to_fit: None,
+        }
+    }
+}
+
+const MAX_SNIPPET_LEN: usize = 40;
+
+/// The expression an error points at, rendered and cut to a short snippet so
+/// errors don't hold on to whole subtrees.
 #[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ErrorContext {
+    pub snippet: String,
+}
+
+/// Collects formatted text until it grows past `MAX_SNIPPET_LEN` characters,
+/// then fails the write so formatting stops early.
+#[derive(Default)]
+struct SnippetWriter {
+    snippet: String,
+    len: usize,
+    truncated: bool,
+}
+
+impl std::fmt::Write for SnippetWriter {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        for c in s.chars() {
+            if self.len == MAX_SNIPPET_LEN {
+                self.truncated = true;
+                return Err(std::fmt::Error);
+            }
+            self.snippet.push(c);
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+impl From<&Exp> for ErrorContext {
+    fn from(exp: &Exp) -> Self {
+        use std::fmt::Write;
+
+        let mut writer = SnippetWriter::default();
+        // Only fails once the snippet is full.
+        let _ = write!(writer, "{}", exp);
+        let mut snippet = writer.snippet;
+        if writer.truncated {
+            snippet.push_str("...");
+        }
+        ErrorContext { snippet }
+    }
+}
+
+impl From<Exp> for ErrorContext {
+    fn from(exp: Exp) -> Self {
+        ErrorContext::from(&exp)
+    }
+}
+
+impl std::fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.snippet)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum RenderTreeError {
-    ExpectedList(Exp),
-    ExpectInteger(Exp),
-    ExpectedSymbol(&'static str, Exp),
-    ExpectedString(Exp),
-    InvalidLength(Exp),
+    ExpectedList(ErrorContext),
+    ExpectInteger(ErrorContext),
+    ExpectFloat(ErrorContext),
+    ExpectedSymbol(&'static str, ErrorContext),
+    ExpectedString(ErrorContext),
+    InvalidLength(ErrorContext),
     InvalidDirection(String),
+    InvalidConstraints(Vec<Constraint>),
+    IncludeCycle(String),
+    IncludeFailed(String, String),
+    ColumnMismatch(usize, ErrorContext),
+    UnboundVariable(String),
+    MaxDepthExceeded(usize),
+    NegativeValue(ErrorContext),
+    RepeatLimitExceeded(usize),
+    ExpansionLimitExceeded(usize),
+    ZeroDenominator(ErrorContext),
+    /// An error inside a container, with the path from the outermost
+    /// container down to the failing node.
+    Located(Vec<String>, Box<RenderTreeError>),
+    /// From `json::from_json`. Always present so matching on this enum
+    /// doesn't depend on the `json` feature.
+    InvalidJson(String),
+}
+
+impl RenderTreeError {
+    /// Records that the error happened inside `segment`, e.g. `stack` or
+    /// `child[2]`.
+    pub fn within(self, segment: impl Into<String>) -> Self {
+        match self {
+            RenderTreeError::Located(mut path, error) => {
+                path.insert(0, segment.into());
+                RenderTreeError::Located(path, error)
+            }
+            error => RenderTreeError::Located(vec![segment.into()], Box::new(error)),
+        }
+    }
+
+    /// The error itself, without the path to where it happened.
+    pub fn root(&self) -> &RenderTreeError {
+        match self {
+            RenderTreeError::Located(_, error) => error.root(),
+            error => error,
+        }
+    }
 }
 
+impl std::fmt::Display for RenderTreeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderTreeError::ExpectedList(exp) => write!(f, "expected a list, found {}", exp),
+            RenderTreeError::ExpectInteger(exp) => write!(f, "expected an integer, found {}", exp),
+            RenderTreeError::ExpectFloat(exp) => write!(f, "expected a number, found {}", exp),
+            RenderTreeError::ExpectedSymbol(expected, exp) => {
+                write!(f, "expected symbol '{}', found {}", expected, exp)
+            }
+            RenderTreeError::ExpectedString(exp) => write!(f, "expected a string, found {}", exp),
+            RenderTreeError::InvalidLength(exp) => {
+                write!(f, "wrong number of elements in {}", exp)
+            }
+            RenderTreeError::InvalidDirection(direction) => write!(
+                f,
+                "invalid direction '{}', expected horizontal or vertical",
+                direction
+            ),
+            RenderTreeError::InvalidConstraints(constraints) => write!(
+                f,
+                "percentage constraints sum to more than 100 in {:?}",
+                constraints
+            ),
+            RenderTreeError::IncludeCycle(path) => write!(f, "include cycle through {}", path),
+            RenderTreeError::IncludeFailed(path, reason) => {
+                write!(f, "failed to include {}: {}", path, reason)
+            }
+            RenderTreeError::ColumnMismatch(expected, row) => {
+                write!(f, "expected {} columns, found {}", expected, row)
+            }
+            RenderTreeError::UnboundVariable(name) => write!(f, "unbound variable '{}'", name),
+            RenderTreeError::NegativeValue(exp) => {
+                write!(f, "expected a non-negative integer, found {}", exp)
+            }
+            RenderTreeError::RepeatLimitExceeded(count) => write!(
+                f,
+                "cannot repeat {} times, the limit is {}",
+                count, MAX_REPEAT
+            ),
+            RenderTreeError::ExpansionLimitExceeded(limit) => {
+                write!(f, "repeats expand to more than {} nodes", limit)
+            }
+            RenderTreeError::Located(path, error) => {
+                write!(f, "{}: {}", path.join(" > "), error)
+            }
+            RenderTreeError::ZeroDenominator(exp) => {
+                write!(f, "ratio denominator must be positive in {}", exp)
+            }
+            RenderTreeError::InvalidJson(message) => write!(f, "invalid json: {}", message),
+            RenderTreeError::MaxDepthExceeded(max_depth) => {
+                write!(f, "tree is nested more than {} nodes deep", max_depth)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderTreeError {}
+
 pub fn create_integer(exp: &Exp) -> Result<i64> {
     exp.as_integer()
-        .ok_or(RenderTreeError::ExpectInteger(exp.clone()))
+        .ok_or_else(|| RenderTreeError::ExpectInteger(exp.into()))
+}
+
+pub fn create_float(exp: &Exp) -> Result<f64> {
+    if let Some(value) = exp.as_integer() {
+        return Ok(value as f64);
+    }
+
+    // Decimal numbers are read as symbols; strings and `nan`/`inf` are not
+    // numbers.
+    exp.as_symbol()
+        .and_then(|symbol| symbol.parse::<f64>().ok())
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| RenderTreeError::ExpectFloat(exp.into()))
+}
+
+pub fn create_alignment(exp: &Exp) -> Result<Alignment> {
+    match exp.as_symbol() {
+        Some("center") => Ok(Alignment::Center),
+        Some("left") => Ok(Alignment::Left),
+        Some("right") => Ok(Alignment::Right),
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "center | left | right",
+            exp.into(),
+        )),
+    }
 }
 
 pub fn create_list_with_len(exp: &Exp, len: usize) -> Result<&[Exp]> {
     let elems = exp
         .as_list()
-        .ok_or(RenderTreeError::ExpectedList(exp.clone()))?;
+        .ok_or_else(|| RenderTreeError::ExpectedList(exp.into()))?;
     if elems.len() != len {
-        return Err(RenderTreeError::InvalidLength(exp.clone()));
+        return Err(RenderTreeError::InvalidLength(exp.into()));
     }
     Ok(elems)
 }
@@ -63,54 +491,219 @@ pub fn create_list_with_len(exp: &Exp, len: usize) -> Result<&[Exp]> {
 pub fn create_list_with_minlen(exp: &Exp, minlen: usize) -> Result<&[Exp]> {
     let elems = exp
         .as_list()
-        .ok_or(RenderTreeError::ExpectedList(exp.clone()))?;
+        .ok_or_else(|| RenderTreeError::ExpectedList(exp.into()))?;
     if elems.len() < minlen {
-        return Err(RenderTreeError::InvalidLength(exp.clone()));
+        return Err(RenderTreeError::InvalidLength(exp.into()));
     }
     Ok(elems)
 }
 
 pub fn check_symbol(exp: &Exp, expected: &'static str) -> Result<()> {
     if exp.as_symbol() != Some(expected) {
-        return Err(RenderTreeError::ExpectedSymbol(expected, exp.clone()));
+        return Err(RenderTreeError::ExpectedSymbol(expected, exp.into()));
     }
     Ok(())
 }
 
-pub fn create_render_tree(exp: &Exp) -> Result<RenderTree> {
-    create_block(exp)
-        .or_else(|_| create_stack(exp))
-        .or_else(|_| create_text(exp))
+/// Parsed trees own all of their text, so they can be kept and rendered
+/// every frame after `exp` has been dropped. The tree is built for an empty
+/// area; use `create_render_tree_ctx` to build it for a known size.
+pub fn create_render_tree(exp: &Exp) -> Result<RenderTree<'static>> {
+    let ctx = RenderContext::default();
+    create_render_tree_ctx(&expand_repeats(exp, ctx.max_depth)?, &ctx)
 }
 
-pub fn create_render_layer(exp: &Exp) -> Result<RenderLayer> {
-    let elems = exp
-        .as_list()
-        .ok_or(RenderTreeError::ExpectedList(exp.clone()))?;
+/// Like `create_render_tree`, resolving `(if var then else)` nodes against
+/// `env`.
+pub fn create_render_tree_with_env(
+    exp: &Exp,
+    env: &HashMap<String, bool>,
+) -> Result<RenderTree<'static>> {
+    let ctx = RenderContext {
+        env: Some(env),
+        ..RenderContext::default()
+    };
+    create_render_tree_ctx(&expand_repeats(exp, ctx.max_depth)?, &ctx)
+}
+
+pub fn create_render_tree_ctx(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let Some(elems) = exp.as_list() else {
+        return create_text(exp, ctx);
+    };
+
+    if ctx.depth >= ctx.max_depth {
+        return Err(RenderTreeError::MaxDepthExceeded(ctx.max_depth));
+    }
+    let ctx = &RenderContext {
+        depth: ctx.depth + 1,
+        ..*ctx
+    };
+
+    match elems.first().and_then(|e| e.as_symbol()) {
+        Some("block") => create_block(exp, ctx),
+        Some("stack") => create_stack(exp, ctx),
+        Some("list") => create_list(exp, ctx),
+        Some("paragraph") => create_paragraph(exp),
+        Some("text") => create_text(exp, ctx),
+        Some("columns-at") => create_columns_at(exp),
+        Some("float") => create_float_node(exp, ctx),
+        Some("clear") => create_clear(exp),
+        Some("spacer") => create_spacer(exp),
+        Some("gauge") => create_gauge(exp),
+        Some("vgauge") => create_vgauge(exp, ctx),
+        Some("marquee") => create_marquee(exp),
+        Some("input") => create_input(exp),
+        Some("log") => create_log(exp),
+        Some("tabs") => create_tabs(exp),
+        Some("table") => create_table(exp, ctx),
+        Some("scrollbar") => create_scrollbar(exp),
+        Some("sparkline") => create_sparkline(exp),
+        Some("chart") => create_chart(exp, ctx),
+        Some("canvas") => create_canvas(exp, ctx),
+        Some("if") => create_if(exp, ctx),
+        Some("when-wider" | "when-taller") => create_responsive(exp, ctx),
+        _ => Err(RenderTreeError::ExpectedSymbol("node kind", exp.into())),
+    }
+}
+
+pub fn create_render_layer(exp: &Exp) -> Result<RenderLayer<'static>> {
+    create_render_layer_ctx(exp, &RenderContext::default())
+}
 
+pub fn create_render_layer_ctx(exp: &Exp, ctx: &RenderContext) -> Result<RenderLayer<'static>> {
+    let exp = &expand_repeats(exp, ctx.max_depth)?;
+    let elems = create_list_with_minlen(exp, 1)?;
     if elems[0].as_symbol() != Some("layer") {
-        return Err(RenderTreeError::ExpectedSymbol("layer", exp.clone()));
+        return Err(RenderTreeError::ExpectedSymbol("layer", exp.into()));
     }
 
-    let trees = elems
+    let is_scale_to_fit = elems
+        .get(1)
+        .and_then(|e| e.as_list())
+        .and_then(|e| e.first())
+        .and_then(|e| e.as_symbol())
+        == Some("scale-to-fit");
+    let (scale_to_fit, children) = if is_scale_to_fit {
+        let size = create_scale_to_fit(&elems[1])
+            .map_err(|err| err.within("scale-to-fit").within("layer"))?;
+        (Some(size), &elems[2..])
+    } else {
+        (None, &elems[1..])
+    };
+
+    let trees = children
         .iter()
-        .skip(1)
-        .map(|e| create_render_tree(e))
+        .enumerate()
+        .map(|(i, e)| {
+            create_render_tree_ctx(e, ctx)
+                .map_err(|err| err.within(format!("child[{}]", i)).within("layer"))
+        })
         .collect::<Result<_>>()?;
 
-    Ok(RenderLayer { trees })
+    Ok(RenderLayer {
+        trees,
+        scale_to_fit,
+    })
+}
+
+fn create_scale_to_fit(exp: &Exp) -> Result<(u16, u16)> {
+    let elems = create_list_with_len(exp, 3)?;
+    check_symbol(&elems[0], "scale-to-fit")?;
+
+    let dimension = |exp: &Exp| {
+        u16::try_from(create_integer(exp)?).map_err(|_| RenderTreeError::NegativeValue(exp.into()))
+    };
+
+    Ok((dimension(&elems[1])?, dimension(&elems[2])?))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ratatui::layout::Constraint;
+    use crate::renderer::render_tree;
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
 
     fn parse(str: &str) -> Exp {
         let mut parser = topogi_lang::parser::Parser::new(str);
         parser.parse_exp().unwrap()
     }
 
+    #[test]
+    fn test_tree_outlives_source() {
+        let tree = {
+            let source = String::from(r#"(block "title" "content" (style (border all)))"#);
+            let exp = parse(&source);
+            create_render_tree(&exp).unwrap()
+        };
+
+        let mut terminal = Terminal::new(TestBackend::new(11, 3)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        terminal.backend().assert_buffer(&Buffer::with_lines([
+            "┌title────┐",
+            "│content  │",
+            "└─────────┘",
+        ]));
+    }
+
+    #[test]
+    fn test_default_context_is_empty() {
+        assert_eq!(RenderContext::default().area, Rect::default());
+
+        let tree = create_render_tree(&parse(r#"(when-wider 1 "wide" "narrow")"#)).unwrap();
+        let RenderTree::Responsive(breakpoint, _, _) = tree else {
+            panic!("expected a responsive node");
+        };
+        assert!(!breakpoint.matches(RenderContext::default().area));
+    }
+
+    #[test]
+    fn test_max_depth() {
+        let source = format!(
+            "{}\"content\"{}",
+            "(block \"title\" ".repeat(1_000),
+            ")".repeat(1_000)
+        );
+        let exp = parse(&source);
+        assert_eq!(
+            create_render_tree(&exp).map_err(|e| e.root().clone()),
+            Err(RenderTreeError::MaxDepthExceeded(DEFAULT_MAX_DEPTH))
+        );
+        assert_eq!(
+            create_render_layer(&parse(&format!("(layer {})", source))),
+            Err(RenderTreeError::MaxDepthExceeded(DEFAULT_MAX_DEPTH))
+        );
+
+        let shallow = parse(r#"(block "a" (block "b" "c"))"#);
+        let ctx = RenderContext {
+            max_depth: 1,
+            ..RenderContext::default()
+        };
+        assert_eq!(
+            create_render_tree_ctx(&shallow, &ctx),
+            Err(RenderTreeError::MaxDepthExceeded(1).within("block"))
+        );
+    }
+
+    #[test]
+    fn test_layer_malformed_input() {
+        let exp = parse("()");
+        assert_eq!(
+            create_render_layer(&exp),
+            Err(RenderTreeError::InvalidLength(exp.clone().into()))
+        );
+
+        let exp = parse(r#""just a string""#);
+        assert_eq!(
+            create_render_layer(&exp),
+            Err(RenderTreeError::ExpectedList(exp.clone().into()))
+        );
+    }
+
     #[test]
     fn test_layer() {
         let exp = parse(
@@ -125,21 +718,179 @@ mod tests {
             Ok(RenderLayer {
                 trees: vec![
                     RenderTree::Block(
-                        Block::default().title("title1"),
+                        BlockStyle::default().title("title1"),
                         Box::new(RenderTree::Text(Text::raw("content1")))
                     ),
                     RenderTree::Stack(
                         Direction::Horizontal,
+                        StackOptions::default(),
                         vec![StackElement::new(
                             Constraint::Length(3),
                             RenderTree::Block(
-                                Block::default().title("title2"),
+                                BlockStyle::default().title("title2"),
                                 Box::new(RenderTree::Text(Text::raw("content2")))
                             )
                         )]
                     )
-                ]
+                ],
+                scale_to_fit: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_layer_scale_to_fit() {
+        let exp = parse(r#"(layer (scale-to-fit 80 24) "content")"#);
+        assert_eq!(
+            create_render_layer(&exp),
+            Ok(RenderLayer {
+                trees: vec![RenderTree::Text(Text::raw("content"))],
+                scale_to_fit: Some((80, 24)),
             })
         );
     }
+
+    #[test]
+    fn test_layer_malformed_scale_to_fit() {
+        let exp = parse(r#"(layer (scale-to-fit -1 10) "content")"#);
+        assert_eq!(
+            create_render_layer(&exp),
+            Err(RenderTreeError::Located(
+                vec!["layer".to_string(), "scale-to-fit".to_string()],
+                Box::new(RenderTreeError::NegativeValue(Exp::Integer(-1).into()))
+            ))
+        );
+
+        let exp = parse(r#"(layer (scale-to-fit 80 x) "content")"#);
+        assert_eq!(
+            create_render_layer(&exp).map_err(|err| err.root().clone()),
+            Err(RenderTreeError::ExpectInteger(
+                Exp::Symbol("x".to_string()).into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_malformed_node_is_not_text() {
+        let exp = parse(r#"(block "x")"#);
+        assert_eq!(
+            create_render_tree(&exp),
+            Err(RenderTreeError::InvalidLength(exp.clone().into()))
+        );
+
+        let exp = parse(r#"(stack horizontal)"#);
+        assert_eq!(
+            create_render_tree(&exp),
+            Err(RenderTreeError::InvalidLength(exp.clone().into()))
+        );
+
+        let exp = parse(r#"(layer "nested")"#);
+        assert_eq!(
+            create_render_tree(&exp),
+            Err(RenderTreeError::ExpectedSymbol(
+                "node kind",
+                exp.clone().into()
+            ))
+        );
+
+        let exp = parse(r#""plain""#);
+        assert_eq!(
+            create_render_tree(&exp),
+            Ok(RenderTree::Text(Text::raw("plain")))
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let exp = parse(r#"(stack vertical)"#);
+        assert_eq!(
+            RenderTreeError::ExpectedSymbol("block", exp.clone().into()).to_string(),
+            format!("expected symbol 'block', found {}", exp)
+        );
+        assert_eq!(
+            RenderTreeError::InvalidDirection("diagonal".to_string()).to_string(),
+            "invalid direction 'diagonal', expected horizontal or vertical"
+        );
+    }
+
+    #[test]
+    fn test_error_context_snippet() {
+        let short = parse(r#"(stack vertical)"#);
+        assert_eq!(ErrorContext::from(&short).snippet, short.to_string());
+
+        let long = parse(r#"(text "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")"#);
+        let snippet = ErrorContext::from(&long).snippet;
+        assert_eq!(snippet.chars().count(), MAX_SNIPPET_LEN + 3);
+        assert!(snippet.ends_with("..."));
+
+        let wide = parse(&format!("(list {})", "\"item\" ".repeat(10_000)));
+        let full = wide.to_string();
+        let cut = full.char_indices().nth(MAX_SNIPPET_LEN).unwrap().0;
+        assert_eq!(
+            ErrorContext::from(&wide).snippet,
+            format!("{}...", &full[..cut])
+        );
+    }
+
+    #[test]
+    fn test_layer_from_iter() {
+        assert!(RenderLayer::default().is_empty());
+
+        let layer: RenderLayer = ["a", "b"]
+            .into_iter()
+            .map(|s| RenderTree::Text(Text::raw(s)))
+            .collect();
+        assert_eq!(layer.len(), 2);
+        assert_eq!(
+            layer.iter().collect::<Vec<_>>(),
+            vec![
+                &RenderTree::Text(Text::raw("a")),
+                &RenderTree::Text(Text::raw("b"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk() {
+        let exp = parse(
+            r#"(stack vertical ((length 1) "a") ((length 3) (block "title" (stack horizontal ((length 1) "b") ((length 1) (paragraph "c"))))))"#,
+        );
+        let mut tree = create_render_tree(&exp).unwrap();
+
+        let mut texts = Vec::new();
+        tree.walk(&mut |node| match node {
+            RenderTree::Text(text) => texts.push(text.to_string()),
+            RenderTree::Paragraph { text, .. } => texts.push(text.clone()),
+            _ => {}
+        });
+        assert_eq!(texts, vec!["a", "b", "c"]);
+
+        tree.walk_mut(&mut |node| {
+            if let RenderTree::Text(_) = node {
+                *node = RenderTree::Empty;
+            }
+        });
+        let mut count = 0;
+        tree.walk(&mut |node| {
+            if let RenderTree::Text(_) = node {
+                count += 1;
+            }
+        });
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn test_create_float() {
+        assert_eq!(create_float(&parse("3.14")), Ok(3.14));
+        assert_eq!(create_float(&parse("0")), Ok(0.0));
+
+        for source in ["pi", "nan", "inf", r#""0.5""#] {
+            let exp = parse(source);
+            assert_eq!(
+                create_float(&exp),
+                Err(RenderTreeError::ExpectFloat(exp.clone().into()))
+            );
+        }
+    }
 }
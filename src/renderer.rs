@@ -1,33 +1,805 @@
+use std::borrow::Cow;
+
 use ratatui::{
-    layout::{Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Text},
+    widgets::{
+        Clear, Gauge, List, ListItem, ListState, Paragraph, Row, Scrollbar, ScrollbarState,
+        Sparkline, Table, Tabs, Wrap,
+    },
     Frame,
 };
 
-use crate::render_tree::{RenderLayer, RenderTree};
+use crate::{
+    align::aligned_area,
+    block::FOCUSED_BORDER_STYLE,
+    canvas::canvas_widget,
+    chart::chart_widget,
+    float::float_area,
+    input::input_window,
+    marquee::marquee_window,
+    render_tree::{RenderLayer, RenderTree},
+    responsive::Breakpoint,
+    stack::{self, StackElement, StackOptions},
+};
 
 pub fn render_tree(tree: &RenderTree, frame: &mut Frame, area: Rect) {
+    if area.is_empty() {
+        return;
+    }
+
     match tree {
         RenderTree::Text(text) => frame.render_widget(text, area),
         RenderTree::Block(block, content) => {
             render_tree(content, frame, block.inner(area));
-            frame.render_widget(block, area);
+            frame.render_widget(block.widget(), area);
         }
-        RenderTree::Stack(direction, stack_elems) => {
-            let constraints = stack_elems.iter().map(|e| e.constraint).collect::<Vec<_>>();
-            let layout = Layout::default()
-                .direction(*direction)
-                .constraints(constraints)
-                .split(area);
-
-            for (content, area) in stack_elems.iter().zip(layout.iter()) {
-                render_tree(&content.content, frame, *area);
+        RenderTree::Focusable {
+            focused, content, ..
+        } => match content.as_ref() {
+            RenderTree::Block(block, inner) if *focused => {
+                render_tree(inner, frame, block.inner(area));
+                frame.render_widget(block.widget().border_style(FOCUSED_BORDER_STYLE), area);
+            }
+            _ => render_tree(content, frame, area),
+        },
+        RenderTree::Blink { visible, content } => {
+            if *visible {
+                render_tree(content, frame, area);
+            }
+        }
+        RenderTree::Stack(direction, options, stack_elems) => {
+            if let Some(color) = options.background {
+                frame
+                    .buffer_mut()
+                    .set_style(area, Style::default().bg(color));
+            }
+            let areas = split_stack(*direction, *options, stack_elems, area);
+            for (content, area) in stack_elems.iter().zip(areas) {
+                render_tree(&content.content, frame, area);
+            }
+        }
+        RenderTree::VGauge(gauge) => frame.render_widget(gauge, area),
+        RenderTree::Marquee { text, offset } => {
+            let window = marquee_window(text, *offset, area.width as usize);
+            frame.render_widget(Text::raw(window), area);
+        }
+        RenderTree::Input {
+            value,
+            cursor,
+            active,
+        } => {
+            let (visible, column) = input_window(value, *cursor, area.width as usize);
+            frame.render_widget(Text::raw(visible), area);
+            if *active {
+                frame.set_cursor(area.x + column as u16, area.y);
             }
         }
+        RenderTree::Responsive(breakpoint, content, otherwise) => {
+            if breakpoint.matches(area) {
+                render_tree(content, frame, area);
+            } else if let Some(otherwise) = otherwise {
+                render_tree(otherwise, frame, area);
+            }
+        }
+        RenderTree::List {
+            items,
+            highlight_symbol,
+            focused,
+            selected,
+            zebra,
+            show_count,
+        } => {
+            let (area, count_area) = match show_count {
+                true if area.height > 1 => {
+                    let [list, count] =
+                        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+                    (list, Some(count))
+                }
+                _ => (area, None),
+            };
+            let mut list = List::new(
+                items
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| ListItem::new(item.as_str()).style(zebra_style(i, *zebra))),
+            );
+            if let Some(symbol) = highlight_symbol {
+                list = list.highlight_symbol(symbol);
+            }
+            if *focused {
+                list = list.highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            }
+            let mut state = ListState::default().with_selected(*selected);
+            frame.render_stateful_widget(list, area, &mut state);
+            if let Some(count_area) = count_area {
+                let line = count_line(state.offset(), area.height as usize, items.len());
+                frame.render_widget(
+                    Line::styled(line, Style::default().add_modifier(Modifier::DIM)),
+                    count_area,
+                );
+            }
+        }
+        RenderTree::Paragraph {
+            text,
+            wrap,
+            trim,
+            scroll,
+            hanging_indent,
+        } => {
+            let paragraph = if !*wrap {
+                Paragraph::new(text.as_str())
+            } else if *hanging_indent > 0 {
+                let rows = hanging_wrap(text, area.width as usize, *hanging_indent as usize);
+                Paragraph::new(rows.join("\n"))
+            } else {
+                Paragraph::new(text.as_str()).wrap(Wrap { trim: *trim })
+            };
+            frame.render_widget(paragraph.scroll(*scroll), area);
+        }
+        RenderTree::Log { lines, follow } => {
+            let text = lines.join("\n");
+            let scroll = if *follow {
+                let height: u16 = lines
+                    .iter()
+                    .map(|line| wrapped_height(line, area.width as usize))
+                    .fold(0, u16::saturating_add);
+                height.saturating_sub(area.height)
+            } else {
+                0
+            };
+            let paragraph = Paragraph::new(text)
+                .wrap(Wrap { trim: true })
+                .scroll((scroll, 0));
+            frame.render_widget(paragraph, area);
+        }
+        RenderTree::Gauge { ratio, label } => {
+            let mut gauge = Gauge::default().ratio(*ratio);
+            if let Some(label) = label {
+                gauge = gauge.label(label.as_str());
+            }
+            frame.render_widget(gauge, area);
+        }
+        RenderTree::Float {
+            area: float,
+            content,
+        } => render_tree(content, frame, float_area(*float, area)),
+        RenderTree::Clear => frame.render_widget(Clear, area),
+        RenderTree::Empty => {}
+        RenderTree::Tabs { titles, selected } => {
+            let tabs = Tabs::new(titles.iter().map(String::as_str))
+                .select(*selected)
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+            frame.render_widget(tabs, area);
+        }
+        RenderTree::Table {
+            headers,
+            rows,
+            widths,
+            zebra,
+        } => {
+            let rows = rows.iter().enumerate().map(|(i, row)| {
+                Row::new(row.iter().map(String::as_str)).style(zebra_style(i, *zebra))
+            });
+            let table = Table::new(rows, widths.iter().copied())
+                .header(Row::new(headers.iter().map(String::as_str)));
+            frame.render_widget(table, area);
+        }
+        RenderTree::Scrollbar {
+            orientation,
+            position,
+            content_length,
+        } => {
+            let mut state = ScrollbarState::new(*content_length).position(*position);
+            frame.render_stateful_widget(Scrollbar::new(orientation.clone()), area, &mut state);
+        }
+        RenderTree::Sparkline { data, max } => {
+            let mut sparkline = Sparkline::default().data(data);
+            if let Some(max) = max {
+                sparkline = sparkline.max(*max);
+            }
+            frame.render_widget(sparkline, area);
+        }
+        RenderTree::Chart(datasets) => frame.render_widget(chart_widget(datasets), area),
+        RenderTree::Canvas {
+            x_bounds,
+            y_bounds,
+            shapes,
+        } => frame.render_widget(canvas_widget(*x_bounds, *y_bounds, shapes), area),
+        RenderTree::Aligned {
+            horizontal,
+            vertical,
+            content,
+        } => render_tree(
+            content,
+            frame,
+            aligned_area(content, area, *horizontal, *vertical),
+        ),
+    }
+}
+
+fn split_stack(
+    direction: Direction,
+    options: StackOptions,
+    stack_elems: &[StackElement],
+    area: Rect,
+) -> Vec<Rect> {
+    let constraints = stack_elems
+        .iter()
+        .map(|e| match e.constraint {
+            Constraint::Min(min) if e.auto_min && direction == Direction::Vertical => {
+                Constraint::Min(min.max(measure_height(&e.content, area.width)))
+            }
+            constraint => constraint,
+        })
+        .collect::<Vec<_>>();
+    stack::split_stack(direction, options, &constraints, area)
+}
+
+/// The `(show-count)` footer of a list of `total` single-row items scrolled
+/// to `offset` in a viewport `height` rows tall.
+fn count_line(offset: usize, height: usize, total: usize) -> String {
+    let visible = total.saturating_sub(offset).min(height);
+    if visible == 0 {
+        return format!("showing 0 of {}", total);
+    }
+    format!("showing {}-{} of {}", offset + 1, offset + visible, total)
+}
+
+/// The style of row `i` of a list or table striped with `zebra`: every
+/// other row, starting with the second, gets the background.
+fn zebra_style(i: usize, zebra: Option<Color>) -> Style {
+    match zebra {
+        Some(color) if i % 2 == 1 => Style::default().bg(color),
+        _ => Style::default(),
     }
 }
 
+/// Renders the layer's trees back-to-front: later trees draw over earlier
+/// ones, and only the cells they touch are overwritten.
 pub fn render_layer(layer: &RenderLayer, frame: &mut Frame, area: Rect) {
-    for tree in layer.iter() {
-        render_tree(tree, frame, area);
+    if area.is_empty() {
+        return;
+    }
+
+    for tree in scaled_trees(layer, area) {
+        render_tree(&tree, frame, area);
+    }
+}
+
+/// The layer's trees as they are drawn into `area`: with `(scale-to-fit w
+/// h)` and an area larger than `w` x `h`, percentages are turned into fills.
+fn scaled_trees<'l, 'a>(
+    layer: &'l RenderLayer<'a>,
+    area: Rect,
+) -> impl Iterator<Item = Cow<'l, RenderTree<'a>>> {
+    let scale = layer
+        .scale_to_fit()
+        .is_some_and(|(width, height)| area.width > width || area.height > height);
+    layer.iter().map(move |tree| {
+        if scale {
+            Cow::Owned(fit_percentages(tree))
+        } else {
+            Cow::Borrowed(tree)
+        }
+    })
+}
+
+fn fit_percentages<'a>(tree: &RenderTree<'a>) -> RenderTree<'a> {
+    match tree {
+        RenderTree::Stack(direction, options, stack_elems) => RenderTree::Stack(
+            *direction,
+            *options,
+            stack_elems
+                .iter()
+                .map(|e| StackElement {
+                    constraint: match e.constraint {
+                        Constraint::Percentage(p) => Constraint::Fill(p),
+                        constraint => constraint,
+                    },
+                    content: e.content.clone(),
+                    auto_min: e.auto_min,
+                })
+                .collect(),
+        ),
+        _ => tree.clone(),
+    }
+}
+
+/// A node whose content doesn't fit the area it was given. `path` locates
+/// it the way `RenderTreeError::Located` does, e.g. `layer > child[0] >
+/// stack > child[1]`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OverflowReport {
+    pub path: Vec<String>,
+    pub area: Rect,
+    pub content_width: usize,
+    pub content_height: usize,
+}
+
+pub fn layer_overflows(layer: &RenderLayer, area: Rect) -> Vec<OverflowReport> {
+    let mut reports = Vec::new();
+    for (i, tree) in scaled_trees(layer, area).enumerate() {
+        let path = vec!["layer".to_string(), format!("child[{}]", i)];
+        tree_overflows(&tree, area, &path, &mut reports);
+    }
+    reports
+}
+
+fn child_path(path: &[String], segments: &[&str]) -> Vec<String> {
+    path.iter()
+        .cloned()
+        .chain(segments.iter().map(|s| s.to_string()))
+        .collect()
+}
+
+fn tree_overflows(
+    tree: &RenderTree,
+    area: Rect,
+    path: &[String],
+    reports: &mut Vec<OverflowReport>,
+) {
+    match tree {
+        RenderTree::Text(text) => {
+            if text.width() > area.width as usize || text.height() > area.height as usize {
+                reports.push(OverflowReport {
+                    path: path.to_vec(),
+                    area,
+                    content_width: text.width(),
+                    content_height: text.height(),
+                });
+            }
+        }
+        RenderTree::Paragraph {
+            text, wrap: false, ..
+        } => tree_overflows(&RenderTree::Text(Text::raw(text)), area, path, reports),
+        RenderTree::Paragraph { wrap: true, .. } => {
+            let height = measure_height(tree, area.width);
+            if height > area.height {
+                reports.push(OverflowReport {
+                    path: path.to_vec(),
+                    area,
+                    content_width: area.width as usize,
+                    content_height: height as usize,
+                });
+            }
+        }
+        RenderTree::Block(block, content) => tree_overflows(
+            content,
+            block.inner(area),
+            &child_path(path, &["block"]),
+            reports,
+        ),
+        RenderTree::Focusable { content, .. } => tree_overflows(content, area, path, reports),
+        RenderTree::Blink { visible, content } => {
+            if *visible {
+                tree_overflows(content, area, path, reports);
+            }
+        }
+        RenderTree::Stack(direction, options, stack_elems) => {
+            let areas = split_stack(*direction, *options, stack_elems, area);
+            for (i, (content, area)) in stack_elems.iter().zip(areas).enumerate() {
+                let path = child_path(path, &["stack", &format!("child[{}]", i)]);
+                tree_overflows(&content.content, area, &path, reports);
+            }
+        }
+        RenderTree::Float {
+            area: float,
+            content,
+        } => tree_overflows(
+            content,
+            float_area(*float, area),
+            &child_path(path, &["float"]),
+            reports,
+        ),
+        RenderTree::Aligned {
+            horizontal,
+            vertical,
+            content,
+        } => tree_overflows(
+            content,
+            aligned_area(content, area, *horizontal, *vertical),
+            path,
+            reports,
+        ),
+        RenderTree::Responsive(breakpoint, content, otherwise) => {
+            let path = child_path(
+                path,
+                &[match breakpoint {
+                    Breakpoint::Wider(_) => "when-wider",
+                    Breakpoint::Taller(_) => "when-taller",
+                }],
+            );
+            if breakpoint.matches(area) {
+                tree_overflows(content, area, &path, reports);
+            } else if let Some(otherwise) = otherwise {
+                tree_overflows(otherwise, area, &path, reports);
+            }
+        }
+        RenderTree::VGauge(_)
+        | RenderTree::Gauge { .. }
+        | RenderTree::Clear
+        | RenderTree::Empty
+        | RenderTree::Marquee { .. }
+        | RenderTree::Input { .. }
+        | RenderTree::List { .. }
+        | RenderTree::Tabs { .. }
+        | RenderTree::Table { .. }
+        | RenderTree::Scrollbar { .. }
+        | RenderTree::Sparkline { .. }
+        | RenderTree::Chart(_)
+        | RenderTree::Canvas { .. }
+        | RenderTree::Log { .. } => {}
+    }
+}
+
+/// The number of rows `tree` needs when rendered `width` columns wide, for
+/// sizing `length` constraints to their content. Overlays and widgets that
+/// stretch to any height count as zero rows.
+pub fn measure_height(tree: &RenderTree, width: u16) -> u16 {
+    match tree {
+        RenderTree::Text(text) => text.height() as u16,
+        RenderTree::Paragraph {
+            text,
+            wrap: true,
+            hanging_indent,
+            ..
+        } if *hanging_indent > 0 => {
+            hanging_wrap(text, width as usize, *hanging_indent as usize).len() as u16
+        }
+        RenderTree::Paragraph {
+            text, wrap: true, ..
+        } => text
+            .lines()
+            .map(|line| wrapped_height(line, width as usize))
+            .sum(),
+        RenderTree::Paragraph { text, .. } => text.lines().count() as u16,
+        RenderTree::Log { lines, .. } => lines
+            .iter()
+            .map(|line| wrapped_height(line, width as usize))
+            .sum(),
+        RenderTree::Block(block, content) => {
+            let probe = Rect::new(0, 0, width, 10);
+            let inner = block.inner(probe);
+            let chrome = probe.height - inner.height;
+            measure_height(content, inner.width).saturating_add(chrome)
+        }
+        RenderTree::Stack(direction, options, stack_elems) => {
+            let margin = options.margin.vertical.saturating_mul(2);
+            let children = match direction {
+                Direction::Vertical => {
+                    let gaps = stack_elems.len().saturating_sub(1) as u16;
+                    stack_elems
+                        .iter()
+                        .map(|e| measure_height(&e.content, width))
+                        .fold(options.spacing.saturating_mul(gaps), u16::saturating_add)
+                }
+                Direction::Horizontal => {
+                    let areas =
+                        split_stack(*direction, *options, stack_elems, Rect::new(0, 0, width, 1));
+                    stack_elems
+                        .iter()
+                        .zip(areas)
+                        .map(|(e, area)| measure_height(&e.content, area.width))
+                        .max()
+                        .unwrap_or(0)
+                }
+            };
+            children.saturating_add(margin)
+        }
+        RenderTree::Responsive(breakpoint, content, otherwise) => {
+            if breakpoint.matches(Rect::new(0, 0, width, 0)) {
+                measure_height(content, width)
+            } else {
+                otherwise.as_ref().map_or(0, |o| measure_height(o, width))
+            }
+        }
+        RenderTree::Aligned { content, .. }
+        | RenderTree::Focusable { content, .. }
+        | RenderTree::Blink { content, .. } => measure_height(content, width),
+        RenderTree::List {
+            items, show_count, ..
+        } => items.len() as u16 + u16::from(*show_count),
+        RenderTree::Table { rows, .. } => rows.len() as u16 + 1,
+        RenderTree::Marquee { .. }
+        | RenderTree::Input { .. }
+        | RenderTree::Gauge { .. }
+        | RenderTree::Tabs { .. }
+        | RenderTree::Sparkline { .. } => 1,
+        RenderTree::Float { .. }
+        | RenderTree::Clear
+        | RenderTree::Empty
+        | RenderTree::VGauge(_)
+        | RenderTree::Scrollbar { .. }
+        | RenderTree::Chart(_)
+        | RenderTree::Canvas { .. } => 0,
+    }
+}
+
+/// Rows a single line takes when word-wrapped to `width` columns. Words
+/// longer than the width are broken across rows.
+fn wrapped_height(line: &str, width: usize) -> u16 {
+    if width == 0 {
+        return 0;
+    }
+
+    let mut rows = 1;
+    let mut current = 0;
+    for word in line.split_whitespace() {
+        let word_width = Line::raw(word).width();
+        if current == 0 {
+            current = word_width;
+        } else if current + 1 + word_width <= width {
+            current += 1 + word_width;
+        } else {
+            rows += 1;
+            current = word_width;
+        }
+        while current > width {
+            rows += 1;
+            current -= width;
+        }
+    }
+    rows
+}
+
+/// Word-wraps `text` to `width` columns like `wrapped_height` does, starting
+/// every row after the first of each line with `indent` spaces. Words wider
+/// than a row are clipped rather than broken.
+fn hanging_wrap(text: &str, width: usize, indent: usize) -> Vec<String> {
+    let indent = indent.min(width.saturating_sub(1));
+    let mut rows = Vec::new();
+    for line in text.lines() {
+        let first = rows.len();
+        let mut row = String::new();
+        for word in line.split_whitespace() {
+            let limit = if rows.len() == first {
+                width
+            } else {
+                width - indent
+            };
+            if !row.is_empty() {
+                if Line::raw(row.as_str()).width() + 1 + Line::raw(word).width() <= limit {
+                    row.push(' ');
+                } else {
+                    rows.push(std::mem::take(&mut row));
+                }
+            }
+            row.push_str(word);
+        }
+        rows.push(row);
+        for row in &mut rows[first + 1..] {
+            row.insert_str(0, &" ".repeat(indent));
+        }
+    }
+    rows
+}
+
+/// Draws a centered "Terminal too small" message in place of the UI.
+pub fn render_too_small(frame: &mut Frame, area: Rect, min_width: u16, min_height: u16) {
+    let message = format!("Terminal too small (need {}x{})", min_width, min_height);
+    let height = measure_height(
+        &RenderTree::Paragraph {
+            text: message.clone(),
+            wrap: true,
+            trim: true,
+            scroll: (0, 0),
+            hanging_indent: 0,
+        },
+        area.width,
+    )
+    .min(area.height);
+    let message_area = Rect::new(
+        area.x,
+        area.y + (area.height - height) / 2,
+        area.width,
+        height,
+    );
+
+    let paragraph = Paragraph::new(message)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, message_area);
+}
+
+/// The header, body and footer areas `render_chrome` splits `area` into.
+pub fn chrome_areas(header: bool, footer: bool, area: Rect) -> (Option<Rect>, Rect, Option<Rect>) {
+    let [header_area, body_area, footer_area] = Layout::vertical([
+        Constraint::Length(if header { 3 } else { 0 }),
+        Constraint::Fill(1),
+        Constraint::Length(if footer { 1 } else { 0 }),
+    ])
+    .areas(area);
+    (
+        header.then_some(header_area),
+        body_area,
+        footer.then_some(footer_area),
+    )
+}
+
+pub fn render_chrome(
+    header: Option<&RenderTree>,
+    body: &RenderTree,
+    footer: Option<&RenderTree>,
+    frame: &mut Frame,
+    area: Rect,
+) {
+    let (header_area, body_area, footer_area) =
+        chrome_areas(header.is_some(), footer.is_some(), area);
+    if let Some((header, area)) = header.zip(header_area) {
+        render_tree(header, frame, area);
+    }
+    render_tree(body, frame, body_area);
+    if let Some((footer, area)) = footer.zip(footer_area) {
+        render_tree(footer, frame, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_tree::{create_render_layer, create_render_tree};
+    use ratatui::{backend::TestBackend, Terminal};
+    use topogi_lang::ast::Exp;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_render_zero_area() {
+        let exp = parse(
+            r#"(layer
+                 (block "title"
+                   (stack vertical ((length 3) "content"))
+                   (style (border all))))"#,
+        );
+        let layer = create_render_layer(&exp).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(0, 0)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_layer(&layer, frame, area);
+            })
+            .unwrap();
+        assert!(terminal.backend().buffer().area.is_empty());
+    }
+
+    #[test]
+    fn test_render_chrome() {
+        let header =
+            create_render_tree(&parse(r#"(block "App" "" (style (border all)))"#)).unwrap();
+        let body = create_render_tree(&parse(r#""body""#)).unwrap();
+        let footer = create_render_tree(&parse(r#""status""#)).unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(10, 8)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_chrome(Some(&header), &body, Some(&footer), frame, area);
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).symbol(), "┌");
+        assert_eq!(buffer.get(0, 2).symbol(), "└");
+        assert_eq!(buffer.get(0, 3).symbol(), "b");
+        assert_eq!(buffer.get(0, 7).symbol(), "s");
+
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_chrome(None, &body, None, frame, area);
+            })
+            .unwrap();
+        assert_eq!(terminal.backend().buffer().get(0, 0).symbol(), "b");
+    }
+
+    #[test]
+    fn test_split_stack_stays_within_area() {
+        let elems = vec![
+            StackElement::new(Constraint::Length(100), RenderTree::Text(Text::raw("a"))),
+            StackElement::new(Constraint::Percentage(80), RenderTree::Text(Text::raw("b"))),
+            StackElement::new(Constraint::Min(30), RenderTree::Text(Text::raw("c"))),
+        ];
+        let area = Rect::new(5, 2, 20, 4);
+        for child in split_stack(Direction::Horizontal, StackOptions::default(), &elems, area) {
+            assert_eq!(child.intersection(area), child);
+            assert!(child.right() <= area.right());
+            assert!(child.bottom() <= area.bottom());
+        }
+    }
+
+    #[test]
+    fn test_measure_height() {
+        let tree = create_render_tree(&parse(
+            r#"(paragraph "the quick brown fox jumps" (wrap true))"#,
+        ))
+        .unwrap();
+        assert_eq!(measure_height(&tree, 10), 3);
+        assert_eq!(measure_height(&tree, 40), 1);
+
+        let tree = create_render_tree(&parse(
+            r#"(block "outer" (stack vertical
+                 ((length 1) "a")
+                 ((length 2) (block "inner" "b" (style (border all))))) (style (border all)))"#,
+        ))
+        .unwrap();
+        assert_eq!(measure_height(&tree, 10), 1 + 3 + 2);
+    }
+
+    #[test]
+    fn test_layer_overflows() {
+        let exp = parse(
+            r#"(layer
+                 (stack vertical
+                   ((length 1) "fits")
+                   ((length 1) "this line is too long")))"#,
+        );
+        let layer = create_render_layer(&exp).unwrap();
+        assert_eq!(
+            layer_overflows(&layer, Rect::new(0, 0, 10, 2)),
+            vec![OverflowReport {
+                path: ["layer", "child[0]", "stack", "child[1]"]
+                    .map(String::from)
+                    .to_vec(),
+                area: Rect::new(0, 1, 10, 1),
+                content_width: 21,
+                content_height: 1,
+            }]
+        );
+
+        let exp = parse(
+            r#"(layer (block "t" (paragraph "one two three" (wrap true)) (style (border all))))"#,
+        );
+        let layer = create_render_layer(&exp).unwrap();
+        assert_eq!(
+            layer_overflows(&layer, Rect::new(0, 0, 7, 4)),
+            vec![OverflowReport {
+                path: ["layer", "child[0]", "block"].map(String::from).to_vec(),
+                area: Rect::new(1, 1, 5, 2),
+                content_width: 5,
+                content_height: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_overflows_follow_scale_to_fit() {
+        let stack = r#"(stack horizontal
+                         ((length 4) "abcd")
+                         ((percentage 25) "0123456789abcde")
+                         ((percentage 25) "y"))"#;
+        let plain = create_render_layer(&parse(&format!("(layer {})", stack))).unwrap();
+        assert_eq!(layer_overflows(&plain, Rect::new(0, 0, 40, 1)).len(), 1);
+
+        let scaled =
+            create_render_layer(&parse(&format!("(layer (scale-to-fit 20 1) {})", stack))).unwrap();
+        assert_eq!(layer_overflows(&scaled, Rect::new(0, 0, 40, 1)), vec![]);
+    }
+
+    #[test]
+    fn test_scale_to_fit() {
+        let exp = parse(
+            r#"(layer (scale-to-fit 20 1)
+                 (stack horizontal
+                   ((length 4) "abcd")
+                   ((percentage 25) "x")
+                   ((percentage 25) "y")))"#,
+        );
+        let layer = create_render_layer(&exp).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(40, 1)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_layer(&layer, frame, area);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).symbol(), "a");
+        assert_eq!(buffer.get(4, 0).symbol(), "x");
+        assert_eq!(buffer.get(22, 0).symbol(), "y");
     }
 }
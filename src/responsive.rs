@@ -0,0 +1,123 @@
+use ratatui::layout::Rect;
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    create_integer, create_list_with_minlen, create_render_tree_ctx, RenderContext, RenderTree,
+    RenderTreeError, Result,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Breakpoint {
+    Wider(u16),
+    Taller(u16),
+}
+
+impl Breakpoint {
+    pub fn matches(&self, area: Rect) -> bool {
+        match self {
+            Breakpoint::Wider(width) => area.width > *width,
+            Breakpoint::Taller(height) => area.height > *height,
+        }
+    }
+}
+
+pub fn create_responsive(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 3)?;
+    if elems.len() > 4 {
+        return Err(RenderTreeError::InvalidLength(exp.into()));
+    }
+
+    let breakpoint: fn(u16) -> Breakpoint = match elems[0].as_symbol() {
+        Some("when-wider") => Breakpoint::Wider,
+        Some("when-taller") => Breakpoint::Taller,
+        _ => {
+            return Err(RenderTreeError::ExpectedSymbol(
+                "when-wider | when-taller",
+                exp.into(),
+            ))
+        }
+    };
+    let breakpoint = breakpoint(create_integer(&elems[1])? as u16);
+
+    let located = |e: RenderTreeError| e.within(elems[0].to_string());
+    let content = create_render_tree_ctx(&elems[2], ctx).map_err(located)?;
+    let otherwise = match elems.get(3) {
+        Some(otherwise) => Some(Box::new(
+            create_render_tree_ctx(otherwise, ctx).map_err(located)?,
+        )),
+        None => None,
+    };
+
+    Ok(RenderTree::Responsive(
+        breakpoint,
+        Box::new(content),
+        otherwise,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_tree;
+    use ratatui::{backend::TestBackend, buffer::Buffer, text::Text, Terminal};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_responsive() {
+        let exp = parse(r#"(when-wider 100 "sidebar")"#);
+        assert_eq!(
+            create_responsive(&exp, &RenderContext::default()),
+            Ok(RenderTree::Responsive(
+                Breakpoint::Wider(100),
+                Box::new(RenderTree::Text(Text::raw("sidebar"))),
+                None
+            ))
+        );
+
+        let exp = parse(r#"(when-taller 10 "tall" "short")"#);
+        assert_eq!(
+            create_responsive(&exp, &RenderContext::default()),
+            Ok(RenderTree::Responsive(
+                Breakpoint::Taller(10),
+                Box::new(RenderTree::Text(Text::raw("tall"))),
+                Some(Box::new(RenderTree::Text(Text::raw("short"))))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render_responsive() {
+        let tree = create_responsive(
+            &parse(r#"(when-wider 10 "wide" "narrow")"#),
+            &RenderContext::default(),
+        )
+        .unwrap();
+
+        let mut terminal = Terminal::new(TestBackend::new(12, 1)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["wide        "]));
+
+        let mut terminal = Terminal::new(TestBackend::new(8, 1)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["narrow  "]));
+    }
+}
@@ -0,0 +1,105 @@
+use ratatui::widgets::ScrollbarOrientation;
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_integer, create_list_with_len, RenderTree, RenderTreeError, Result,
+};
+
+fn create_orientation(exp: &Exp) -> Result<ScrollbarOrientation> {
+    match exp.as_symbol() {
+        Some("vertical") => Ok(ScrollbarOrientation::VerticalRight),
+        Some("horizontal") => Ok(ScrollbarOrientation::HorizontalBottom),
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "vertical | horizontal",
+            exp.into(),
+        )),
+    }
+}
+
+fn create_clause(exp: &Exp, name: &'static str) -> Result<usize> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], name)?;
+
+    let value = create_integer(&elems[1])?;
+    usize::try_from(value).map_err(|_| RenderTreeError::NegativeValue((&elems[1]).into()))
+}
+
+/// `(scrollbar vertical (position n) (length n))`. A position past the end
+/// of the content is clamped to it; negative values are an error.
+pub fn create_scrollbar(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_len(exp, 4)?;
+    check_symbol(&elems[0], "scrollbar")?;
+
+    let orientation = create_orientation(&elems[1])?;
+    let position = create_clause(&elems[2], "position")?;
+    let content_length = create_clause(&elems[3], "length")?;
+
+    Ok(RenderTree::Scrollbar {
+        orientation,
+        position: position.min(content_length),
+        content_length,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_scrollbar() {
+        let exp = parse("(scrollbar vertical (position 3) (length 20))");
+        assert_eq!(
+            create_scrollbar(&exp),
+            Ok(RenderTree::Scrollbar {
+                orientation: ScrollbarOrientation::VerticalRight,
+                position: 3,
+                content_length: 20,
+            })
+        );
+
+        let exp = parse("(scrollbar horizontal (position 30) (length 20))");
+        assert_eq!(
+            create_scrollbar(&exp),
+            Ok(RenderTree::Scrollbar {
+                orientation: ScrollbarOrientation::HorizontalBottom,
+                position: 20,
+                content_length: 20,
+            })
+        );
+    }
+
+    #[test]
+    fn test_negative_values() {
+        let exp = parse("(scrollbar vertical (position -3) (length 20))");
+        assert_eq!(
+            create_scrollbar(&exp),
+            Err(RenderTreeError::NegativeValue(Exp::Integer(-3).into()))
+        );
+
+        let exp = parse("(scrollbar vertical (position 0) (length -1))");
+        assert_eq!(
+            create_scrollbar(&exp),
+            Err(RenderTreeError::NegativeValue(Exp::Integer(-1).into()))
+        );
+    }
+
+    #[test]
+    fn test_render_scrollbar_in_stack() {
+        let exp = parse(
+            r#"(layer
+                 (stack horizontal
+                   ((fill 1) (paragraph "content"))
+                   ((length 1) (scrollbar vertical (position 0) (length 20)))))"#,
+        );
+        let buffer = UIEngine::render_to_buffer(&exp, 8, 5).unwrap();
+        assert_eq!(buffer.get(7, 0).symbol(), "↑");
+        assert_eq!(buffer.get(7, 1).symbol(), "█");
+        assert_eq!(buffer.get(7, 4).symbol(), "↓");
+    }
+}
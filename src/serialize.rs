@@ -0,0 +1,307 @@
+//! Serde adapters for the ratatui types a `RenderTree` holds. Enums and
+//! structs with public fields are mirrored with `#[serde(remote = ...)]`;
+//! `Text` and block titles go through owned copies of their spans and lines.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Flex, Margin, Rect},
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{
+        block::{Position, Title},
+        BorderType, Borders, Padding, ScrollbarOrientation,
+    },
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Constraint")]
+pub enum ConstraintDef {
+    Min(u16),
+    Max(u16),
+    Length(u16),
+    Percentage(u16),
+    Ratio(u32, u32),
+    Fill(u16),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Direction")]
+pub enum DirectionDef {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Flex")]
+pub enum FlexDef {
+    Legacy,
+    Start,
+    End,
+    Center,
+    SpaceBetween,
+    SpaceAround,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Alignment")]
+pub enum AlignmentDef {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Position")]
+pub enum PositionDef {
+    Top,
+    Bottom,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "BorderType")]
+pub enum BorderTypeDef {
+    Plain,
+    Rounded,
+    Double,
+    Thick,
+    QuadrantInside,
+    QuadrantOutside,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "ScrollbarOrientation")]
+pub enum ScrollbarOrientationDef {
+    VerticalRight,
+    VerticalLeft,
+    HorizontalBottom,
+    HorizontalTop,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Margin")]
+pub struct MarginDef {
+    pub horizontal: u16,
+    pub vertical: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Padding")]
+pub struct PaddingDef {
+    pub left: u16,
+    pub right: u16,
+    pub top: u16,
+    pub bottom: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Rect")]
+pub struct RectDef {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ConstraintRef(#[serde(with = "ConstraintDef")] Constraint);
+
+#[derive(Serialize, Deserialize)]
+struct AlignmentRef(#[serde(with = "AlignmentDef")] Alignment);
+
+#[derive(Serialize, Deserialize)]
+struct PositionRef(#[serde(with = "PositionDef")] Position);
+
+/// `Vec<Constraint>`, e.g. a table's column widths.
+pub mod constraints {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        constraints: &[Constraint],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(constraints.iter().map(|c| ConstraintRef(*c)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Constraint>, D::Error> {
+        let constraints = Vec::<ConstraintRef>::deserialize(deserializer)?;
+        Ok(constraints.into_iter().map(|c| c.0).collect())
+    }
+}
+
+/// `Borders` as its bit set.
+pub mod borders {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(borders: &Borders, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(borders.bits())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Borders, D::Error> {
+        u8::deserialize(deserializer).map(Borders::from_bits_truncate)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BordersRef(#[serde(with = "borders")] Borders);
+
+/// `Option<Borders>`, e.g. a block's borders while focused.
+pub mod option_borders {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        borders: &Option<Borders>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        borders.map(BordersRef).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Borders>, D::Error> {
+        Ok(Option::<BordersRef>::deserialize(deserializer)?.map(|b| b.0))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SpanDef {
+    content: String,
+    style: Style,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LineDef {
+    spans: Vec<SpanDef>,
+    style: Style,
+    alignment: Option<AlignmentRef>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TextDef {
+    lines: Vec<LineDef>,
+    style: Style,
+    alignment: Option<AlignmentRef>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TitleDef {
+    content: LineDef,
+    alignment: Option<AlignmentRef>,
+    position: Option<PositionRef>,
+}
+
+impl LineDef {
+    fn new(line: &Line) -> Self {
+        LineDef {
+            spans: line
+                .spans
+                .iter()
+                .map(|span| SpanDef {
+                    content: span.content.to_string(),
+                    style: span.style,
+                })
+                .collect(),
+            style: line.style,
+            alignment: line.alignment.map(AlignmentRef),
+        }
+    }
+
+    fn into_line(self) -> Line<'static> {
+        Line {
+            spans: self
+                .spans
+                .into_iter()
+                .map(|span| Span::styled(span.content, span.style))
+                .collect(),
+            style: self.style,
+            alignment: self.alignment.map(|a| a.0),
+        }
+    }
+}
+
+/// `Text`, with the content of each span copied.
+pub mod text {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(text: &Text, serializer: S) -> Result<S::Ok, S::Error> {
+        TextDef {
+            lines: text.lines.iter().map(LineDef::new).collect(),
+            style: text.style,
+            alignment: text.alignment.map(AlignmentRef),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, 'a, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Text<'a>, D::Error> {
+        let text = TextDef::deserialize(deserializer)?;
+        Ok(Text {
+            lines: text.lines.into_iter().map(LineDef::into_line).collect(),
+            style: text.style,
+            alignment: text.alignment.map(|a| a.0),
+        })
+    }
+}
+
+/// A block's `Vec<Title>`.
+pub mod titles {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(titles: &[Title], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(titles.iter().map(|title| TitleDef {
+            content: LineDef::new(&title.content),
+            alignment: title.alignment.map(AlignmentRef),
+            position: title.position.map(PositionRef),
+        }))
+    }
+
+    pub fn deserialize<'de, 'a, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Title<'a>>, D::Error> {
+        let titles = Vec::<TitleDef>::deserialize(deserializer)?;
+        Ok(titles
+            .into_iter()
+            .map(|title| Title {
+                content: title.content.into_line(),
+                alignment: title.alignment.map(|a| a.0),
+                position: title.position.map(|p| p.0),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::render_tree::{create_render_tree_ctx, RenderContext, RenderTree};
+    use ratatui::layout::Rect;
+    use topogi_lang::ast::Exp;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let exp = parse(
+            r#"(stack vertical (margin 1 0) (flex center)
+                 ((length 3)
+                   (block ((span "*" (fg green)) (span " db")) "content"
+                     (style (border all) (border-type rounded) (padding 0 1 0 0)
+                            (titles (right "ok")) (title-position bottom))))
+                 ((fill 1)
+                   (stack horizontal
+                     ((ratio 1 3) (text "a"))
+                     ((percentage 50) (block "inner" (table (headers "h") (row "x")))))
+                     ((min 2) (gauge 0.5)))))"#,
+        );
+        let tree =
+            create_render_tree_ctx(&exp, &RenderContext::new(Rect::new(0, 0, 40, 20))).unwrap();
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let decoded: RenderTree = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, tree);
+    }
+}
@@ -0,0 +1,42 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{check_symbol, create_list_with_len, RenderTree, Result};
+
+pub fn create_spacer(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_len(exp, 1)?;
+    check_symbol(&elems[0], "spacer")?;
+
+    Ok(RenderTree::Empty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+    use ratatui::buffer::Buffer;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_spacer() {
+        assert_eq!(create_spacer(&parse("(spacer)")), Ok(RenderTree::Empty));
+    }
+
+    #[test]
+    fn test_render_spacer() {
+        let exp = parse(
+            r#"(layer
+                 (stack horizontal
+                   ((fill 1) (spacer))
+                   ((length 2) "ab")
+                   ((fill 1) (spacer))))"#,
+        );
+        assert_eq!(
+            UIEngine::render_to_buffer(&exp, 6, 1).unwrap(),
+            Buffer::with_lines(["  ab  "])
+        );
+    }
+}
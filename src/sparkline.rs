@@ -0,0 +1,89 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_integer, create_list_with_len, create_list_with_minlen, RenderTree,
+    RenderTreeError, Result,
+};
+
+fn create_value(exp: &Exp) -> Result<u64> {
+    let value = create_integer(exp)?;
+    u64::try_from(value).map_err(|_| RenderTreeError::NegativeValue(exp.into()))
+}
+
+fn create_max(exp: &Exp) -> Result<u64> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "max")?;
+
+    create_value(&elems[1])
+}
+
+/// `(sparkline [(max n)] v1 v2 ...)`. Without `max` the scale follows the
+/// largest value.
+pub fn create_sparkline(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    check_symbol(&elems[0], "sparkline")?;
+
+    let mut values = &elems[1..];
+    let mut max = None;
+    if let Some(Ok(value)) = values.first().map(create_max) {
+        max = Some(value);
+        values = &values[1..];
+    }
+
+    let data = values
+        .iter()
+        .map(create_value)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(RenderTree::Sparkline { data, max })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_sparkline() {
+        assert_eq!(
+            create_sparkline(&parse("(sparkline 1 4 2 8 5)")),
+            Ok(RenderTree::Sparkline {
+                data: vec![1, 4, 2, 8, 5],
+                max: None
+            })
+        );
+        assert_eq!(
+            create_sparkline(&parse("(sparkline (max 10) 1 4)")),
+            Ok(RenderTree::Sparkline {
+                data: vec![1, 4],
+                max: Some(10)
+            })
+        );
+    }
+
+    #[test]
+    fn test_negative_value() {
+        assert!(create_sparkline(&parse("(sparkline 1 -2 3)")).is_err());
+
+        let negative = Exp::Integer(-2);
+        assert_eq!(
+            create_value(&negative),
+            Err(RenderTreeError::NegativeValue(negative.clone().into()))
+        );
+    }
+
+    #[test]
+    fn test_render_sparkline() {
+        let exp = parse("(layer (sparkline 1 4 2 8 5))");
+        let buffer = UIEngine::render_to_buffer(&exp, 5, 2).unwrap();
+        let drawn = (0..5)
+            .flat_map(|x| (0..2).map(move |y| (x, y)))
+            .filter(|&(x, y)| buffer.get(x, y).symbol() != " ")
+            .count();
+        assert!(drawn > 0);
+    }
+}
@@ -1,15 +1,40 @@
-use ratatui::layout::{Constraint, Direction};
+use ratatui::{
+    layout::{Constraint, Direction, Flex, Layout, Margin, Rect},
+    style::Color,
+};
 use topogi_lang::ast::Exp;
 
-use crate::render_tree::{
-    check_symbol, create_integer, create_list_with_len, create_list_with_minlen,
-    create_render_tree, RenderTree, RenderTreeError, Result,
+use crate::{
+    render_tree::{
+        check_symbol, create_integer, create_list_with_len, create_list_with_minlen,
+        create_render_tree_ctx, ErrorContext, RenderContext, RenderTree, RenderTreeError, Result,
+    },
+    style::{parse_color, Palette},
 };
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StackElement<'a> {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::ConstraintDef"))]
     pub constraint: Constraint,
     pub content: Box<RenderTree<'a>>,
+    /// Set by `(auto-min n)`, whose constraint is `Min(n)`. A vertical stack
+    /// raises it to the content's measured height when laying out.
+    pub auto_min: bool,
+}
+
+/// Layout settings given by clauses between a stack's direction and its
+/// children.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StackOptions {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::MarginDef"))]
+    pub margin: Margin,
+    pub spacing: u16,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::FlexDef"))]
+    pub flex: Flex,
+    pub background: Option<Color>,
+    pub normalize: bool,
 }
 
 impl<'a> StackElement<'a> {
@@ -17,17 +42,41 @@ impl<'a> StackElement<'a> {
         StackElement {
             constraint,
             content: Box::new(content),
+            auto_min: false,
         }
     }
 }
 
-fn create_constraint(exp: &Exp) -> Result<Constraint> {
-    let elems = create_list_with_len(exp, 2)?;
+/// `(ratio n d)`, the only constraint kind taking two values.
+fn create_ratio(exp: &Exp) -> Result<Constraint> {
+    let elems = create_list_with_len(exp, 3)?;
+    check_symbol(&elems[0], "ratio")?;
+
+    let numerator = create_integer(&elems[1])?;
+    let denominator = create_integer(&elems[2])?;
+    if numerator < 0 {
+        return Err(RenderTreeError::NegativeValue(ErrorContext::from(
+            &elems[1],
+        )));
+    }
+    if denominator <= 0 {
+        return Err(RenderTreeError::ZeroDenominator(exp.into()));
+    }
+    Ok(Constraint::Ratio(numerator as u32, denominator as u32))
+}
+
+pub fn create_constraint(exp: &Exp) -> Result<Constraint> {
+    let elems = create_list_with_minlen(exp, 2)?;
 
-    let kind = elems[0].as_symbol().ok_or(RenderTreeError::ExpectedSymbol(
-        "constraint kind",
-        exp.clone(),
-    ))?;
+    let kind = elems[0]
+        .as_symbol()
+        .ok_or_else(|| RenderTreeError::ExpectedSymbol("constraint kind", exp.into()))?;
+    if kind == "ratio" {
+        return create_ratio(exp);
+    }
+    if elems.len() != 2 {
+        return Err(RenderTreeError::InvalidLength(exp.into()));
+    }
 
     match kind {
         "length" => {
@@ -50,27 +99,221 @@ fn create_constraint(exp: &Exp) -> Result<Constraint> {
             let value = create_integer(&elems[1])?;
             Ok(Constraint::Fill(value as u16))
         }
+        "auto-min" => {
+            let value = u16::try_from(create_integer(&elems[1])?)
+                .map_err(|_| RenderTreeError::NegativeValue(ErrorContext::from(&elems[1])))?;
+            Ok(Constraint::Min(value))
+        }
         _ => Err(RenderTreeError::ExpectedSymbol(
             "constraint kind",
-            exp.clone(),
+            exp.into(),
         )),
     }
 }
 
-fn create_stack_element(exp: &Exp) -> Result<StackElement> {
+/// Splits a `(constraint content)` pair, or the same pair written as
+/// `(item constraint content)`.
+fn stack_pair(exp: &Exp) -> Result<(&Exp, &Exp)> {
+    if exp
+        .as_list()
+        .and_then(|elems| elems.first())
+        .and_then(|e| e.as_symbol())
+        == Some("item")
+    {
+        let elems = create_list_with_len(exp, 3)?;
+        return Ok((&elems[1], &elems[2]));
+    }
+
+    let elems = create_list_with_len(exp, 2)?;
+    Ok((&elems[0], &elems[1]))
+}
+
+fn create_stack_element(exp: &Exp, ctx: &RenderContext) -> Result<StackElement<'static>> {
+    let (constraint, content) = stack_pair(exp)?;
+
+    let auto_min = constraint
+        .as_list()
+        .and_then(|elems| elems.first())
+        .and_then(|e| e.as_symbol())
+        == Some("auto-min");
+    let constraint = create_constraint(constraint)?;
+    let content = create_render_tree_ctx(content, ctx)?;
+
+    Ok(StackElement {
+        auto_min,
+        ..StackElement::new(constraint, content)
+    })
+}
+
+fn create_weight(exp: &Exp) -> Result<Constraint> {
+    let elems = create_list_with_len(exp, 2)?;
+
+    let weight = create_integer(&elems[0])?;
+    Ok(Constraint::Fill(weight as u16))
+}
+
+fn create_weighted_element(exp: &Exp, ctx: &RenderContext) -> Result<StackElement<'static>> {
     let elems = create_list_with_len(exp, 2)?;
 
-    let constraint = create_constraint(&elems[0])?;
-    let content = create_render_tree(&elems[1])?;
+    let constraint = create_weight(exp)?;
+    let content = create_render_tree_ctx(&elems[1], ctx)?;
 
     Ok(StackElement::new(constraint, content))
 }
 
+fn weighted_children(exp: &Exp) -> Result<&[Exp]> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "weighted")?;
+
+    Ok(&elems[1..])
+}
+
+/// The constraints a stack child contributes: one for a `(constraint
+/// content)` pair, or one per entry of a `(weighted ...)` group.
+fn child_constraints(exp: &Exp) -> Result<Vec<Constraint>> {
+    match weighted_children(exp) {
+        Ok(children) => children.iter().map(create_weight).collect(),
+        Err(_) => {
+            let (constraint, _) = stack_pair(exp)?;
+            Ok(vec![create_constraint(constraint)?])
+        }
+    }
+}
+
+/// Scales percentages so they sum to 100, as ratios of their total so no
+/// space is lost to rounding.
+fn normalize_percentages(constraints: &[Constraint]) -> Vec<Constraint> {
+    let sum = constraints
+        .iter()
+        .map(|c| match c {
+            Constraint::Percentage(p) => u32::from(*p),
+            _ => 0,
+        })
+        .sum::<u32>();
+    constraints
+        .iter()
+        .map(|&c| match c {
+            Constraint::Percentage(p) if sum > 0 => Constraint::Ratio(u32::from(p), sum),
+            c => c,
+        })
+        .collect()
+}
+
+pub fn split_stack(
+    direction: Direction,
+    options: StackOptions,
+    constraints: &[Constraint],
+    area: Rect,
+) -> Vec<Rect> {
+    let normalized;
+    let constraints = if options.normalize {
+        normalized = normalize_percentages(constraints);
+        &normalized
+    } else {
+        constraints
+    };
+    let layout = Layout::default()
+        .direction(direction)
+        .horizontal_margin(options.margin.horizontal)
+        .vertical_margin(options.margin.vertical)
+        .spacing(options.spacing)
+        .flex(options.flex)
+        .constraints(constraints.iter().copied())
+        .split(area);
+
+    layout
+        .iter()
+        .map(|child| child.intersection(area))
+        .collect()
+}
+
+/// `(margin n)` for the same margin on every side, or `(margin h v)`.
+fn create_margin(exp: &Exp) -> Result<Margin> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "margin")?;
+
+    match elems[1..] {
+        [ref all] => {
+            let all = create_integer(all)? as u16;
+            Ok(Margin::new(all, all))
+        }
+        [ref horizontal, ref vertical] => Ok(Margin::new(
+            create_integer(horizontal)? as u16,
+            create_integer(vertical)? as u16,
+        )),
+        _ => Err(RenderTreeError::InvalidLength(exp.into())),
+    }
+}
+
+fn create_spacing(exp: &Exp) -> Result<u16> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "spacing")?;
+
+    Ok(create_integer(&elems[1])? as u16)
+}
+
+fn create_flex(exp: &Exp) -> Result<Flex> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "flex")?;
+
+    match elems[1].as_symbol() {
+        Some("start") => Ok(Flex::Start),
+        Some("end") => Ok(Flex::End),
+        Some("center") => Ok(Flex::Center),
+        Some("space-between") => Ok(Flex::SpaceBetween),
+        Some("space-around") => Ok(Flex::SpaceAround),
+        Some("legacy") => Ok(Flex::Legacy),
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "start | end | center | space-between | space-around | legacy",
+            ErrorContext::from(&elems[1]),
+        )),
+    }
+}
+
+fn create_normalize(exp: &Exp) -> Result<bool> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "normalize")?;
+
+    match elems[1].as_symbol() {
+        Some("true") => Ok(true),
+        Some("false") => Ok(false),
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "true | false",
+            ErrorContext::from(&elems[1]),
+        )),
+    }
+}
+
+/// `(bg color)`, filling the whole stack area.
+fn create_background(exp: &Exp, palette: Option<&Palette>) -> Result<Color> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "bg")?;
+
+    parse_color(&elems[1], palette)
+}
+
+/// Applies `exp` to `options` if it is a stack option clause, returning
+/// whether it was one.
+fn stack_option(options: &mut StackOptions, exp: &Exp, palette: Option<&Palette>) -> Result<bool> {
+    match exp
+        .as_list()
+        .and_then(|elems| elems.first())
+        .and_then(|e| e.as_symbol())
+    {
+        Some("margin") => options.margin = create_margin(exp)?,
+        Some("spacing") => options.spacing = create_spacing(exp)?,
+        Some("flex") => options.flex = create_flex(exp)?,
+        Some("bg") => options.background = Some(create_background(exp, palette)?),
+        Some("normalize") => options.normalize = create_normalize(exp)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
 fn create_direction(exp: &Exp) -> Result<Direction> {
-    let direction = exp.as_symbol().ok_or(RenderTreeError::ExpectedSymbol(
-        "horizontal or vertical",
-        exp.clone(),
-    ))?;
+    let direction = exp
+        .as_symbol()
+        .ok_or_else(|| RenderTreeError::ExpectedSymbol("horizontal or vertical", exp.into()))?;
     match direction {
         "horizontal" => Ok(Direction::Horizontal),
         "vertical" => Ok(Direction::Vertical),
@@ -78,24 +321,92 @@ fn create_direction(exp: &Exp) -> Result<Direction> {
     }
 }
 
-pub fn create_stack(exp: &Exp) -> Result<RenderTree> {
+/// Checks that no stack in `tree` has percentage constraints summing past 100,
+/// unless it normalizes them.
+pub fn validate_constraints(tree: &RenderTree) -> Result<()> {
+    match tree {
+        RenderTree::Stack(_, options, stack_elems) => {
+            let percentages = stack_elems
+                .iter()
+                .map(|e| match e.constraint {
+                    Constraint::Percentage(p) => u32::from(p),
+                    _ => 0,
+                })
+                .sum::<u32>();
+            if percentages > 100 && !options.normalize {
+                return Err(RenderTreeError::InvalidConstraints(
+                    stack_elems.iter().map(|e| e.constraint).collect(),
+                ));
+            }
+
+            stack_elems
+                .iter()
+                .try_for_each(|e| validate_constraints(&e.content))
+        }
+        RenderTree::Block(_, content)
+        | RenderTree::Float { content, .. }
+        | RenderTree::Aligned { content, .. }
+        | RenderTree::Focusable { content, .. }
+        | RenderTree::Blink { content, .. } => validate_constraints(content),
+        RenderTree::Responsive(_, content, otherwise) => {
+            validate_constraints(content)?;
+            match otherwise {
+                Some(otherwise) => validate_constraints(otherwise),
+                None => Ok(()),
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+pub fn create_stack(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
     let elems = create_list_with_minlen(exp, 3)?;
     check_symbol(&elems[0], "stack")?;
 
     let direction = create_direction(&elems[1])?;
+    let mut options = StackOptions::default();
+    let mut children = &elems[2..];
+    while let Some(option) = children.first() {
+        if !stack_option(&mut options, option, ctx.palette)? {
+            break;
+        }
+        children = &children[1..];
+    }
 
-    let stack_elements = elems
-        .iter()
-        .skip(2)
-        .map(|e| create_stack_element(e))
-        .collect::<Result<Vec<StackElement>>>()?;
+    let mut constraints = Vec::new();
+    for elem in children {
+        constraints.extend(child_constraints(elem)?);
+    }
+    let mut areas = split_stack(direction, options, &constraints, ctx.area).into_iter();
+    let mut next_ctx = || RenderContext {
+        area: areas.next().unwrap_or_default(),
+        ..*ctx
+    };
+
+    let mut stack_elements = Vec::new();
+    let located =
+        |i: usize| move |err: RenderTreeError| err.within(format!("child[{}]", i)).within("stack");
+    for elem in children {
+        if let Ok(children) = weighted_children(elem) {
+            for child in children {
+                let element = create_weighted_element(child, &next_ctx())
+                    .map_err(located(stack_elements.len()))?;
+                stack_elements.push(element);
+            }
+        } else {
+            let element =
+                create_stack_element(elem, &next_ctx()).map_err(located(stack_elements.len()))?;
+            stack_elements.push(element);
+        }
+    }
 
-    Ok(RenderTree::Stack(direction, stack_elements))
+    Ok(RenderTree::Stack(direction, options, stack_elements))
 }
 
 #[cfg(test)]
 mod tests {
-    use ratatui::{text::Text, widgets::Block};
+    use crate::block::BlockStyle;
+    use ratatui::text::Text;
 
     use super::*;
     fn parse(str: &str) -> Exp {
@@ -107,23 +418,82 @@ mod tests {
     fn test_create_constraint() {
         let exp = parse(r#"(length 3)"#);
         assert_eq!(create_constraint(&exp), Ok(Constraint::Length(3)));
+
+        let exp = parse(r#"(ratio 1 3)"#);
+        assert_eq!(create_constraint(&exp), Ok(Constraint::Ratio(1, 3)));
+
+        let exp = parse(r#"(ratio 1 0)"#);
+        assert_eq!(
+            create_constraint(&exp),
+            Err(RenderTreeError::ZeroDenominator(exp.clone().into()))
+        );
+
+        let exp = parse(r#"(length 1 2)"#);
+        assert_eq!(
+            create_constraint(&exp),
+            Err(RenderTreeError::InvalidLength(exp.clone().into()))
+        );
+    }
+
+    #[test]
+    fn test_stack_ratio() {
+        let exp = parse(
+            r#"(layer
+                 (stack horizontal
+                   ((ratio 1 3) (block "" "" (style (border all))))
+                   ((ratio 2 3) (block "" "" (style (border all))))))"#,
+        );
+        assert_eq!(
+            crate::UIEngine::render_to_buffer(&exp, 9, 3).unwrap(),
+            ratatui::buffer::Buffer::with_lines(["┌─┐┌────┐", "│ ││    │", "└─┘└────┘"])
+        );
     }
 
     #[test]
     fn test_create_stack_element() {
         let exp = parse(r#"((length 3) (block "title" "content"))"#);
         assert_eq!(
-            create_stack_element(&exp),
+            create_stack_element(&exp, &RenderContext::default()),
             Ok(StackElement::new(
                 Constraint::Length(3),
                 RenderTree::Block(
-                    Block::default().title("title"),
+                    BlockStyle::default().title("title"),
                     Box::new(RenderTree::Text(Text::raw("content")))
                 )
             ))
         );
     }
 
+    #[test]
+    fn test_stack_item_form() {
+        let pairs = parse(
+            r#"(stack vertical
+                 ((length 3) (block "a" "content"))
+                 ((fill 1) (text "b")))"#,
+        );
+        let items = parse(
+            r#"(stack vertical
+                 (item (length 3) (block "a" "content"))
+                 (item (fill 1) (text "b")))"#,
+        );
+        let expected = create_stack(&pairs, &RenderContext::default());
+        assert!(expected.is_ok());
+        assert_eq!(create_stack(&items, &RenderContext::default()), expected);
+        assert_eq!(
+            create_stack_element(
+                &parse(r#"(item (length 3) "a")"#),
+                &RenderContext::default()
+            ),
+            create_stack_element(&parse(r#"((length 3) "a")"#), &RenderContext::default())
+        );
+
+        let exp = parse(r#"(item (length 3))"#);
+        assert_eq!(
+            create_stack_element(&exp, &RenderContext::default()),
+            Err(RenderTreeError::InvalidLength(exp.clone().into()))
+        );
+    }
+
     #[test]
     fn test_create_stack() {
         let exp = parse(
@@ -133,21 +503,22 @@ mod tests {
                    )"#,
         );
         assert_eq!(
-            create_stack(&exp),
+            create_stack(&exp, &RenderContext::default()),
             Ok(RenderTree::Stack(
                 Direction::Horizontal,
+                StackOptions::default(),
                 vec![
                     StackElement::new(
                         Constraint::Length(3),
                         RenderTree::Block(
-                            Block::default().title("title1"),
+                            BlockStyle::default().title("title1"),
                             Box::new(RenderTree::Text(Text::raw("content1")))
                         )
                     ),
                     StackElement::new(
                         Constraint::Length(3),
                         RenderTree::Block(
-                            Block::default().title("title2"),
+                            BlockStyle::default().title("title2"),
                             Box::new(RenderTree::Text(Text::raw("content2")))
                         )
                     )
@@ -155,4 +526,216 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_create_stack_with_margin() {
+        let exp = parse(r#"(stack vertical (margin 1) ((length 1) "a"))"#);
+        assert_eq!(
+            create_stack(&exp, &RenderContext::default()),
+            Ok(RenderTree::Stack(
+                Direction::Vertical,
+                StackOptions {
+                    margin: Margin::new(1, 1),
+                    ..StackOptions::default()
+                },
+                vec![StackElement::new(
+                    Constraint::Length(1),
+                    RenderTree::Text(Text::raw("a"))
+                )]
+            ))
+        );
+
+        let exp = parse(r#"(stack vertical (margin 2 1) ((length 1) "a"))"#);
+        assert!(matches!(
+            create_stack(&exp, &RenderContext::default()),
+            Ok(RenderTree::Stack(_, options, _)) if options.margin == Margin::new(2, 1)
+        ));
+
+        assert_eq!(
+            split_stack(
+                Direction::Vertical,
+                StackOptions {
+                    margin: Margin::new(2, 1),
+                    ..StackOptions::default()
+                },
+                &[Constraint::Fill(1)],
+                Rect::new(0, 0, 10, 5)
+            ),
+            vec![Rect::new(2, 1, 6, 3)]
+        );
+    }
+
+    #[test]
+    fn test_stack_spacing() {
+        let exp = parse(
+            r#"(layer
+                 (stack horizontal (spacing 1)
+                   ((length 3) (block "" "" (style (border all))))
+                   ((length 3) (block "" "" (style (border all))))))"#,
+        );
+        assert_eq!(
+            crate::UIEngine::render_to_buffer(&exp, 7, 3).unwrap(),
+            ratatui::buffer::Buffer::with_lines(["┌─┐ ┌─┐", "│ │ │ │", "└─┘ └─┘"])
+        );
+    }
+
+    #[test]
+    fn test_stack_flex() {
+        let exp = parse(r#"(layer (stack horizontal (flex center) ((length 2) "ab")))"#);
+        assert_eq!(
+            crate::UIEngine::render_to_buffer(&exp, 6, 1).unwrap(),
+            ratatui::buffer::Buffer::with_lines(["  ab  "])
+        );
+
+        let exp = parse(r#"(stack horizontal (flex sideways) ((length 2) "ab"))"#);
+        assert_eq!(
+            create_stack(&exp, &RenderContext::default()),
+            Err(RenderTreeError::ExpectedSymbol(
+                "start | end | center | space-between | space-around | legacy",
+                parse("sideways").into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_auto_min() {
+        let exp = parse(
+            r#"(layer
+                 (stack vertical
+                   ((auto-min 3) (text "1\n2\n3\n4\n5"))
+                   ((fill 1) "x")))"#,
+        );
+        assert_eq!(
+            crate::UIEngine::render_to_lines(&exp, 1, 8).unwrap(),
+            vec!["1", "2", "3", "4", "5", "x", "", ""]
+        );
+
+        let exp = parse(r#"(layer (stack vertical ((auto-min 3) (text "1\n2")) ((fill 1) "x")))"#);
+        assert_eq!(
+            crate::UIEngine::render_to_lines(&exp, 1, 5).unwrap(),
+            vec!["1", "2", "", "x", ""]
+        );
+
+        let exp = parse("(auto-min -3)");
+        assert_eq!(
+            create_constraint(&exp),
+            Err(RenderTreeError::NegativeValue(parse("-3").into()))
+        );
+    }
+
+    #[test]
+    fn test_stack_background() {
+        let exp = parse(r#"(layer (stack vertical (bg blue) ((length 1) "a")))"#);
+        let buffer = crate::UIEngine::render_to_buffer(&exp, 3, 2).unwrap();
+        assert_eq!(buffer.get(0, 0).symbol(), "a");
+        assert_eq!(buffer.get(0, 0).bg, Color::Blue);
+        assert_eq!(buffer.get(2, 1).bg, Color::Blue);
+    }
+
+    #[test]
+    fn test_stack_normalize() {
+        let areas = |normalize: &str| {
+            let exp = parse(&format!(
+                r#"(stack vertical {}
+                     ((percentage 30) "a")
+                     ((percentage 30) "b")
+                     ((percentage 30) "c"))"#,
+                normalize
+            ));
+            let Ok(RenderTree::Stack(direction, options, stack_elems)) =
+                create_stack(&exp, &RenderContext::default())
+            else {
+                panic!("expected a stack");
+            };
+            let constraints = stack_elems.iter().map(|e| e.constraint).collect::<Vec<_>>();
+            split_stack(direction, options, &constraints, Rect::new(0, 0, 1, 10))
+        };
+
+        assert_eq!(areas("").last().unwrap().bottom(), 9);
+
+        let normalized = areas("(normalize true)");
+        assert_eq!(normalized[0].top(), 0);
+        assert_eq!(normalized.last().unwrap().bottom(), 10);
+        assert!(normalized.windows(2).all(|w| w[0].bottom() == w[1].top()));
+    }
+
+    #[test]
+    fn test_create_weighted_stack() {
+        let exp = parse(r#"(stack horizontal (weighted (3 "child1") (1 "child2")))"#);
+        assert_eq!(
+            create_stack(&exp, &RenderContext::default()),
+            Ok(RenderTree::Stack(
+                Direction::Horizontal,
+                StackOptions::default(),
+                vec![
+                    StackElement::new(Constraint::Fill(3), RenderTree::Text(Text::raw("child1"))),
+                    StackElement::new(Constraint::Fill(1), RenderTree::Text(Text::raw("child2"))),
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_error_path() {
+        let exp = parse(
+            r#"(layer
+                 "header"
+                 (stack vertical
+                   ((length 1) "a")
+                   ((length 1) (stack horizontal ((length 1) (text))))))"#,
+        );
+        let err = crate::render_tree::create_render_layer(&exp).unwrap_err();
+        assert_eq!(
+            err,
+            RenderTreeError::InvalidLength(parse("(text)").into())
+                .within("child[0]")
+                .within("stack")
+                .within("child[1]")
+                .within("stack")
+                .within("child[1]")
+                .within("layer")
+        );
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "layer > child[1] > stack > child[1] > stack > child[0]: wrong number of elements in {}",
+                parse("(text)")
+            )
+        );
+    }
+
+    #[test]
+    fn test_validate_constraints() {
+        let exp = parse(
+            r#"(stack vertical
+                 ((percentage 60) "a")
+                 ((percentage 60) "b")
+                 ((length 3) "c"))"#,
+        );
+        let tree = create_stack(&exp, &RenderContext::default()).unwrap();
+        assert_eq!(
+            validate_constraints(&tree),
+            Err(RenderTreeError::InvalidConstraints(vec![
+                Constraint::Percentage(60),
+                Constraint::Percentage(60),
+                Constraint::Length(3),
+            ]))
+        );
+
+        let exp = parse(
+            r#"(block "title" (stack vertical ((percentage 60) "a") ((percentage 60) "b")))"#,
+        );
+        let tree = crate::block::create_block(&exp, &RenderContext::default()).unwrap();
+        assert!(validate_constraints(&tree).is_err());
+
+        let exp = parse(r#"(stack vertical ((percentage 50) "a") ((percentage 50) "b"))"#);
+        let tree = create_stack(&exp, &RenderContext::default()).unwrap();
+        assert_eq!(validate_constraints(&tree), Ok(()));
+
+        let exp = parse(
+            r#"(stack vertical (normalize true) ((percentage 60) "a") ((percentage 60) "b"))"#,
+        );
+        let tree = create_stack(&exp, &RenderContext::default()).unwrap();
+        assert_eq!(validate_constraints(&tree), Ok(()));
+    }
 }
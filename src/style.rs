@@ -0,0 +1,172 @@
+use std::{collections::HashMap, str::FromStr};
+
+use ratatui::style::{Color, Modifier, Style};
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{check_symbol, create_list_with_len, RenderTreeError, Result};
+
+/// Named colors registered on the engine, e.g. `primary` or `accent`.
+pub type Palette = HashMap<String, Color>;
+
+/// `rgb`, `rrggbb` or `rrggbbaa` hex digits. Alpha is ignored since
+/// terminals can't blend.
+fn parse_hex(hex: &str) -> Option<Color> {
+    let digits = hex
+        .chars()
+        .map(|c| c.to_digit(16).map(|d| d as u8))
+        .collect::<Option<Vec<_>>>()?;
+
+    match digits[..] {
+        [r, g, b] => Some(Color::Rgb(r * 0x11, g * 0x11, b * 0x11)),
+        [r1, r2, g1, g2, b1, b2] | [r1, r2, g1, g2, b1, b2, _, _] => {
+            Some(Color::Rgb((r1 << 4) | r2, (g1 << 4) | g2, (b1 << 4) | b2))
+        }
+        _ => None,
+    }
+}
+
+/// Resolves `exp` through `palette` first, falling back to ANSI names and
+/// hex codes.
+pub fn parse_color(exp: &Exp, palette: Option<&Palette>) -> Result<Color> {
+    let name = exp.to_string();
+    if let Some(color) = palette.and_then(|p| p.get(&name)) {
+        return Ok(*color);
+    }
+    if let Some(hex) = name.strip_prefix('#') {
+        return parse_hex(hex).ok_or_else(|| {
+            RenderTreeError::ExpectedSymbol("#rgb | #rrggbb | #rrggbbaa", exp.into())
+        });
+    }
+    Color::from_str(&name).map_err(|_| RenderTreeError::ExpectedSymbol("color", exp.into()))
+}
+
+pub fn color_clause(style: Style, exp: &Exp, palette: Option<&Palette>) -> Result<Style> {
+    let elems = create_list_with_len(exp, 2)?;
+
+    match elems[0].as_symbol() {
+        Some("fg") => Ok(style.fg(parse_color(&elems[1], palette)?)),
+        Some("bg") => Ok(style.bg(parse_color(&elems[1], palette)?)),
+        _ => Err(RenderTreeError::ExpectedSymbol("fg | bg", exp.into())),
+    }
+}
+
+pub fn parse_modifier(exp: &Exp) -> Result<Modifier> {
+    match exp.as_symbol() {
+        Some("bold") => Ok(Modifier::BOLD),
+        Some("italic") => Ok(Modifier::ITALIC),
+        Some("underline") => Ok(Modifier::UNDERLINED),
+        Some("dim") => Ok(Modifier::DIM),
+        Some("reversed") => Ok(Modifier::REVERSED),
+        Some("blink") => Ok(Modifier::SLOW_BLINK),
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "bold | italic | underline | dim | reversed | blink",
+            exp.into(),
+        )),
+    }
+}
+
+pub fn modifier_clause(style: Style, exp: &Exp) -> Result<Style> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "modifier")?;
+
+    Ok(style.add_modifier(parse_modifier(&elems[1])?))
+}
+
+/// Applies a `(fg c)`, `(bg c)` or `(modifier m)` clause to `style`.
+pub fn style_clause(style: Style, exp: &Exp, palette: Option<&Palette>) -> Result<Style> {
+    let elems = create_list_with_len(exp, 2)?;
+
+    match elems[0].as_symbol() {
+        Some("fg" | "bg") => color_clause(style, exp, palette),
+        Some("modifier") => modifier_clause(style, exp),
+        _ => Err(RenderTreeError::ExpectedSymbol(
+            "fg | bg | modifier",
+            exp.into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color(&parse("red"), None), Ok(Color::Red));
+        assert_eq!(
+            parse_color(&parse(r##""#1a1a1a""##), None),
+            Ok(Color::Rgb(0x1a, 0x1a, 0x1a))
+        );
+        assert!(parse_color(&parse("nocolor"), None).is_err());
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        let color = |hex: &str| parse_color(&parse(&format!("\"{}\"", hex)), None);
+        assert_eq!(color("#fff"), Ok(Color::Rgb(0xff, 0xff, 0xff)));
+        assert_eq!(color("#ffffff"), Ok(Color::Rgb(0xff, 0xff, 0xff)));
+        assert_eq!(color("#a1b"), Ok(Color::Rgb(0xaa, 0x11, 0xbb)));
+        assert_eq!(color("#1a2b3c80"), Ok(Color::Rgb(0x1a, 0x2b, 0x3c)));
+        assert_eq!(
+            color("#12"),
+            Err(RenderTreeError::ExpectedSymbol(
+                "#rgb | #rrggbb | #rrggbbaa",
+                parse(r##""#12""##).into()
+            ))
+        );
+        assert!(color("#ggg").is_err());
+    }
+
+    #[test]
+    fn test_color_clause() {
+        let style = color_clause(Style::default(), &parse("(fg green)"), None).unwrap();
+        let style = color_clause(style, &parse("(bg black)"), None).unwrap();
+        assert_eq!(style, Style::default().fg(Color::Green).bg(Color::Black));
+    }
+
+    #[test]
+    fn test_style_clause() {
+        let style = style_clause(Style::default(), &parse("(modifier bold)"), None).unwrap();
+        let style = style_clause(style, &parse("(modifier italic)"), None).unwrap();
+        let style = style_clause(style, &parse("(fg red)"), None).unwrap();
+        assert_eq!(
+            style,
+            Style::default()
+                .fg(Color::Red)
+                .add_modifier(Modifier::BOLD | Modifier::ITALIC)
+        );
+
+        assert_eq!(
+            style_clause(Style::default(), &parse("(modifier blink)"), None),
+            Ok(Style::default().add_modifier(Modifier::SLOW_BLINK))
+        );
+        assert_eq!(
+            style_clause(Style::default(), &parse("(modifier sparkle)"), None),
+            Err(RenderTreeError::ExpectedSymbol(
+                "bold | italic | underline | dim | reversed | blink",
+                parse("sparkle").into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_palette_color() {
+        let palette = Palette::from([("accent".to_string(), Color::Rgb(0xff, 0x80, 0x00))]);
+        assert_eq!(
+            parse_color(&parse("accent"), Some(&palette)),
+            Ok(Color::Rgb(0xff, 0x80, 0x00))
+        );
+        assert_eq!(parse_color(&parse("red"), Some(&palette)), Ok(Color::Red));
+        assert_eq!(
+            parse_color(&parse("accent"), None),
+            Err(RenderTreeError::ExpectedSymbol(
+                "color",
+                parse("accent").into()
+            ))
+        );
+    }
+}
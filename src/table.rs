@@ -0,0 +1,165 @@
+use ratatui::layout::Constraint;
+use topogi_lang::ast::Exp;
+
+use crate::{
+    list::{create_zebra, is_zebra},
+    render_tree::{
+        check_symbol, create_list_with_minlen, ErrorContext, RenderContext, RenderTree,
+        RenderTreeError, Result,
+    },
+    stack::create_constraint,
+};
+
+fn create_cells(exp: &Exp, kind: &'static str) -> Result<Vec<String>> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    check_symbol(&elems[0], kind)?;
+
+    Ok(elems[1..].iter().map(|e| e.to_string()).collect())
+}
+
+fn create_widths(exp: &Exp) -> Result<Vec<Constraint>> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    check_symbol(&elems[0], "widths")?;
+
+    elems[1..].iter().map(create_constraint).collect()
+}
+
+/// `(table (headers ...) [(widths ...)] [(zebra (bg color))] (row ...)*)`.
+/// Columns share the width evenly unless a `widths` clause gives one
+/// constraint per column.
+pub fn create_table(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "table")?;
+
+    let headers = create_cells(&elems[1], "headers")?;
+    let mut rows = &elems[2..];
+    let mut widths = vec![Constraint::Fill(1); headers.len()];
+    let mut zebra = None;
+    while let Some(first) = rows.first() {
+        if let Ok(given) = create_widths(first) {
+            if given.len() != headers.len() {
+                return Err(RenderTreeError::ColumnMismatch(
+                    headers.len(),
+                    ErrorContext::from(first),
+                ));
+            }
+            widths = given;
+        } else if is_zebra(first) {
+            zebra = Some(create_zebra(first, ctx.palette)?);
+        } else {
+            break;
+        }
+        rows = &rows[1..];
+    }
+
+    let rows = rows
+        .iter()
+        .map(|row| {
+            let cells = create_cells(row, "row")?;
+            if cells.len() != headers.len() {
+                return Err(RenderTreeError::ColumnMismatch(headers.len(), row.into()));
+            }
+            Ok(cells)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(RenderTree::Table {
+        headers,
+        rows,
+        widths,
+        zebra,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UIEngine;
+    use ratatui::{buffer::Buffer, style::Color};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_table() {
+        let exp = parse(r#"(table (headers "Name" "Age") (row "Alice" "30") (row "Bob" "25"))"#);
+        assert_eq!(
+            create_table(&exp, &RenderContext::default()),
+            Ok(RenderTree::Table {
+                headers: vec!["Name".to_string(), "Age".to_string()],
+                rows: vec![
+                    vec!["Alice".to_string(), "30".to_string()],
+                    vec!["Bob".to_string(), "25".to_string()],
+                ],
+                widths: vec![Constraint::Fill(1), Constraint::Fill(1)],
+                zebra: None,
+            })
+        );
+
+        let exp = parse(r#"(table (headers "Name" "Age") (widths (length 6) (fill 1)))"#);
+        assert_eq!(
+            create_table(&exp, &RenderContext::default()),
+            Ok(RenderTree::Table {
+                headers: vec!["Name".to_string(), "Age".to_string()],
+                rows: vec![],
+                widths: vec![Constraint::Length(6), Constraint::Fill(1)],
+                zebra: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_table_column_mismatch() {
+        let exp = parse(r#"(table (headers "Name" "Age") (row "Alice"))"#);
+        assert_eq!(
+            create_table(&exp, &RenderContext::default()),
+            Err(RenderTreeError::ColumnMismatch(
+                2,
+                parse(r#"(row "Alice")"#).into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_render_table() {
+        let exp = parse(
+            r#"(layer
+                 (table (headers "Name" "Age") (widths (length 5) (length 3))
+                   (row "Alice" "30")
+                   (row "Bob" "25")))"#,
+        );
+        assert_eq!(
+            UIEngine::render_to_buffer(&exp, 9, 3).unwrap(),
+            Buffer::with_lines(["Name  Age", "Alice 30 ", "Bob   25 "])
+        );
+    }
+
+    #[test]
+    fn test_zebra_rows() {
+        let exp = parse(
+            r##"(layer
+                 (table (headers "N") (zebra (bg "#1a1a1a"))
+                   (row "a") (row "b") (row "c") (row "d")))"##,
+        );
+        let buffer = UIEngine::render_to_buffer(&exp, 1, 5).unwrap();
+        let zebra = Color::Rgb(0x1a, 0x1a, 0x1a);
+        assert_eq!(buffer.get(0, 0).bg, Color::Reset);
+        for (y, striped) in [(1, false), (2, true), (3, false), (4, true)] {
+            assert_eq!(buffer.get(0, y).bg == zebra, striped);
+        }
+
+        let exp = parse(r##"(layer (list (zebra (bg "#1a1a1a")) "a" "b" "c" "d"))"##);
+        let buffer = UIEngine::render_to_buffer(&exp, 1, 4).unwrap();
+        for (y, striped) in [(0, false), (1, true), (2, false), (3, true)] {
+            assert_eq!(buffer.get(0, y).bg == zebra, striped);
+        }
+
+        let exp = parse(r#"(table (headers "N") (zebra (fg red)) (row "a"))"#);
+        assert_eq!(
+            create_table(&exp, &RenderContext::default()),
+            Err(RenderTreeError::ExpectedSymbol("bg", parse("fg").into()))
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    check_symbol, create_integer, create_list_with_len, create_list_with_minlen, RenderTree, Result,
+};
+
+fn create_selected(exp: &Exp) -> Result<i64> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "selected")?;
+
+    create_integer(&elems[1])
+}
+
+pub fn create_tabs(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 1)?;
+    check_symbol(&elems[0], "tabs")?;
+
+    let mut titles = &elems[1..];
+    let mut selected = 0;
+    if let Some(Ok(index)) = titles.first().map(create_selected) {
+        selected = index;
+        titles = &titles[1..];
+    }
+
+    let titles = titles.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+    let selected = selected.clamp(0, titles.len().saturating_sub(1) as i64) as usize;
+
+    Ok(RenderTree::Tabs { titles, selected })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_tree;
+    use ratatui::{backend::TestBackend, style::Modifier, Terminal};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_tabs() {
+        let exp = parse(r#"(tabs (selected 1) "Home" "Settings" "About")"#);
+        assert_eq!(
+            create_tabs(&exp),
+            Ok(RenderTree::Tabs {
+                titles: vec![
+                    "Home".to_string(),
+                    "Settings".to_string(),
+                    "About".to_string()
+                ],
+                selected: 1,
+            })
+        );
+
+        let exp = parse(r#"(tabs "Home" "Settings")"#);
+        assert_eq!(
+            create_tabs(&exp),
+            Ok(RenderTree::Tabs {
+                titles: vec!["Home".to_string(), "Settings".to_string()],
+                selected: 0,
+            })
+        );
+
+        let exp = parse(r#"(tabs (selected 5) "Home" "Settings")"#);
+        assert_eq!(
+            create_tabs(&exp),
+            Ok(RenderTree::Tabs {
+                titles: vec!["Home".to_string(), "Settings".to_string()],
+                selected: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_tabs() {
+        let tree = create_tabs(&parse(r#"(tabs (selected 1) "Home" "Settings")"#)).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(20, 1)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let row = (0..20)
+            .map(|x| buffer.get(x, 0).symbol())
+            .collect::<String>();
+        let home = row.find("Home").unwrap() as u16;
+        let settings = row.find("Settings").unwrap() as u16;
+        assert!(!buffer.get(home, 0).modifier.contains(Modifier::REVERSED));
+        assert!(buffer
+            .get(settings, 0)
+            .modifier
+            .contains(Modifier::REVERSED));
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+
+use topogi_lang::ast::Exp;
+
+use crate::render_tree::{
+    create_integer, create_list_with_len, ErrorContext, RenderTreeError, Result,
+};
+
+/// The most copies a single `repeat` form may expand to.
+pub const MAX_REPEAT: usize = 1000;
+
+/// The most nodes a whole expansion may produce, so nested `repeat` forms
+/// can't multiply past what a single one allows.
+pub const MAX_EXPANDED_NODES: usize = 100_000;
+
+/// How many lists deep a single node may reach into its children, as a
+/// `(weighted (n child))` stack child does, so list nesting can be bounded
+/// in terms of node depth.
+const LISTS_PER_NODE: usize = 3;
+
+/// Replaces `$name` symbols and strings in `exp` with strings from `env`.
+/// Placeholders missing from `env` are an error when `strict` is set and are
+/// left as written otherwise.
+pub fn substitute(exp: &Exp, env: &HashMap<String, String>, strict: bool) -> Result<Exp> {
+    if let Some(elems) = exp.as_list() {
+        let elems = elems
+            .iter()
+            .map(|e| substitute(e, env, strict))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(Exp::List(elems));
+    }
+
+    let atom = exp.to_string();
+    let Some(name) = atom.strip_prefix('$') else {
+        return Ok(exp.clone());
+    };
+
+    match env.get(name) {
+        Some(value) => Ok(Exp::String(value.clone())),
+        None if strict => Err(RenderTreeError::UnboundVariable(name.to_string())),
+        None => Ok(exp.clone()),
+    }
+}
+
+/// Splices `(repeat n child)` forms into their parent list as `n` copies of
+/// `child`. `(repeat-indexed n child)` also replaces `$i` placeholders in
+/// each copy with its index, starting at 0. Input nested too deep to build
+/// `max_depth` nodes from is rejected before it can overflow the stack.
+pub fn expand_repeats(exp: &Exp, max_depth: usize) -> Result<Exp> {
+    let mut expansion = Expansion {
+        max_depth,
+        nodes: 0,
+    };
+    expansion.expand(exp, max_depth.saturating_mul(LISTS_PER_NODE))
+}
+
+struct Expansion {
+    max_depth: usize,
+    /// Nodes in the output so far, counting every copy.
+    nodes: usize,
+}
+
+impl Expansion {
+    fn count_nodes(&mut self, nodes: usize) -> Result<()> {
+        self.nodes = nodes;
+        if self.nodes > MAX_EXPANDED_NODES {
+            return Err(RenderTreeError::ExpansionLimitExceeded(MAX_EXPANDED_NODES));
+        }
+        Ok(())
+    }
+
+    fn expand(&mut self, exp: &Exp, lists_left: usize) -> Result<Exp> {
+        self.count_nodes(self.nodes + 1)?;
+        let Some(elems) = exp.as_list() else {
+            return Ok(exp.clone());
+        };
+        let Some(lists_left) = lists_left.checked_sub(1) else {
+            return Err(RenderTreeError::MaxDepthExceeded(self.max_depth));
+        };
+
+        let mut expanded = Vec::with_capacity(elems.len());
+        for elem in elems {
+            match repeat_form(elem)? {
+                Some((count, child, indexed)) => {
+                    let before = self.nodes;
+                    let child = self.expand(child, lists_left)?;
+                    let size = self.nodes - before;
+                    self.count_nodes(before.saturating_add(size.saturating_mul(count)))?;
+                    for index in 0..count {
+                        if indexed {
+                            expanded.push(substitute_index(&child, index));
+                        } else {
+                            expanded.push(child.clone());
+                        }
+                    }
+                }
+                None => expanded.push(self.expand(elem, lists_left)?),
+            }
+        }
+        Ok(Exp::List(expanded))
+    }
+}
+
+fn repeat_form(exp: &Exp) -> Result<Option<(usize, &Exp, bool)>> {
+    let indexed = match exp
+        .as_list()
+        .and_then(|elems| elems.first())
+        .and_then(|e| e.as_symbol())
+    {
+        Some("repeat") => false,
+        Some("repeat-indexed") => true,
+        _ => return Ok(None),
+    };
+
+    let elems = create_list_with_len(exp, 3)?;
+    let count = usize::try_from(create_integer(&elems[1])?)
+        .map_err(|_| RenderTreeError::NegativeValue(ErrorContext::from(&elems[1])))?;
+    if count > MAX_REPEAT {
+        return Err(RenderTreeError::RepeatLimitExceeded(count));
+    }
+    Ok(Some((count, &elems[2], indexed)))
+}
+
+/// Replaces a `$i` symbol with the index as an integer, and `$i` inside
+/// strings with its digits. Longer names such as `$id` are left alone.
+fn substitute_index(exp: &Exp, index: usize) -> Exp {
+    match exp {
+        Exp::List(elems) => Exp::List(elems.iter().map(|e| substitute_index(e, index)).collect()),
+        Exp::Symbol(symbol) if symbol == "$i" => Exp::Integer(index as i64),
+        Exp::String(string) => Exp::String(replace_index(string, index)),
+        _ => exp.clone(),
+    }
+}
+
+fn replace_index(string: &str, index: usize) -> String {
+    let mut replaced = String::with_capacity(string.len());
+    let mut rest = string;
+    while let Some(at) = rest.find("$i") {
+        replaced.push_str(&rest[..at]);
+        rest = &rest[at + 2..];
+        if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+            replaced.push_str("$i");
+        } else {
+            replaced.push_str(&index.to_string());
+        }
+    }
+    replaced.push_str(rest);
+    replaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render_tree::DEFAULT_MAX_DEPTH;
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    fn env() -> HashMap<String, String> {
+        HashMap::from([
+            ("username".to_string(), "alice".to_string()),
+            ("status".to_string(), "online".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_substitute_nested() {
+        let exp = parse(
+            r#"(stack vertical
+                 ((length 3) (block $username (text $status)))
+                 ((fill 1) "$username"))"#,
+        );
+        assert_eq!(
+            substitute(&exp, &env(), true),
+            Ok(parse(
+                r#"(stack vertical
+                     ((length 3) (block "alice" (text "online")))
+                     ((fill 1) "alice"))"#
+            ))
+        );
+    }
+
+    #[test]
+    fn test_expand_repeats() {
+        let exp = parse(r#"(layer (repeat 2 (block "item" "x")) "end")"#);
+        assert_eq!(
+            expand_repeats(&exp, DEFAULT_MAX_DEPTH),
+            Ok(parse(
+                r#"(layer (block "item" "x") (block "item" "x") "end")"#
+            ))
+        );
+
+        let exp =
+            parse(r#"(stack vertical (repeat-indexed 3 ((length 1) (block "item-$i" "x"))))"#);
+        assert_eq!(
+            expand_repeats(&exp, DEFAULT_MAX_DEPTH),
+            Ok(parse(
+                r#"(stack vertical
+                     ((length 1) (block "item-0" "x"))
+                     ((length 1) (block "item-1" "x"))
+                     ((length 1) (block "item-2" "x")))"#
+            ))
+        );
+
+        let exp = parse(r#"(layer (repeat 100000 "x"))"#);
+        assert_eq!(
+            expand_repeats(&exp, DEFAULT_MAX_DEPTH),
+            Err(RenderTreeError::RepeatLimitExceeded(100000))
+        );
+
+        let exp = parse(r#"(layer (repeat 1000 (stack vertical (repeat 1000 ((length 1) "x")))))"#);
+        assert_eq!(
+            expand_repeats(&exp, DEFAULT_MAX_DEPTH),
+            Err(RenderTreeError::ExpansionLimitExceeded(MAX_EXPANDED_NODES))
+        );
+    }
+
+    #[test]
+    fn test_substitute_index() {
+        let exp = parse(r#"(stack vertical (repeat-indexed 2 ((length $i) (text "$id-$i" $id))))"#);
+        assert_eq!(
+            expand_repeats(&exp, DEFAULT_MAX_DEPTH),
+            Ok(parse(
+                r#"(stack vertical
+                     ((length 0) (text "$id-0" $id))
+                     ((length 1) (text "$id-1" $id)))"#
+            ))
+        );
+    }
+
+    #[test]
+    fn test_unresolved_placeholder() {
+        let exp = parse(r#"(block "title" (text $missing))"#);
+        assert_eq!(
+            substitute(&exp, &env(), true),
+            Err(RenderTreeError::UnboundVariable("missing".to_string()))
+        );
+        assert_eq!(substitute(&exp, &env(), false), Ok(exp.clone()));
+    }
+}
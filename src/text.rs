@@ -1,16 +1,143 @@
-use ratatui::text::Text;
+use ratatui::{
+    layout::Alignment,
+    style::Style,
+    text::{Line, Span, Text},
+};
 use topogi_lang::ast::Exp;
 
-use crate::render_tree::RenderTree;
-use crate::render_tree::Result;
+use crate::{
+    blink::is_blink,
+    render_tree::{
+        check_symbol, create_alignment, create_integer, create_list_with_len,
+        create_list_with_minlen, ErrorContext, RenderContext, RenderTree, RenderTreeError, Result,
+    },
+    style::{style_clause, Palette},
+};
 
-pub fn create_text(exp: &Exp) -> Result<RenderTree> {
-    Ok(RenderTree::Text(Text::raw(exp.to_string())))
+/// Builds a `Text` from a string atom, one line per newline in the parsed
+/// string. Escapes such as `\n` are left to the parser to decode.
+fn multiline_text(exp: &Exp) -> Text<'static> {
+    Text::raw(exp.to_string())
+}
+
+pub fn create_text(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    if exp.as_list().is_none() {
+        return Ok(RenderTree::Text(multiline_text(exp)));
+    }
+
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "text")?;
+
+    let span_count = elems[1..].iter().take_while(|e| is_span(e)).count();
+    let (mut text, rest) = if span_count > 0 {
+        let spans = elems[1..=span_count]
+            .iter()
+            .map(|e| create_span(e, ctx.palette))
+            .collect::<Result<Vec<_>>>()?;
+        (Text::from(Line::from(spans)), &elems[span_count + 1..])
+    } else {
+        (multiline_text(&elems[1]), &elems[2..])
+    };
+    let blink = rest.last().is_some_and(is_blink);
+    let rest = if blink { &rest[..rest.len() - 1] } else { rest };
+    if rest.len() > 1 {
+        return Err(RenderTreeError::InvalidLength(exp.into()));
+    }
+    if let Some(style) = rest.first() {
+        text = text_style(text, style, ctx.palette)?;
+    }
+
+    let text = RenderTree::Text(text);
+    Ok(if blink {
+        RenderTree::Blink {
+            visible: true,
+            content: Box::new(text),
+        }
+    } else {
+        text
+    })
+}
+
+pub fn is_span(exp: &Exp) -> bool {
+    exp.as_list()
+        .and_then(|elems| elems.first())
+        .and_then(|e| e.as_symbol())
+        == Some("span")
+}
+
+pub fn create_span(exp: &Exp, palette: Option<&Palette>) -> Result<Span<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "span")?;
+
+    let mut style = Style::default();
+    for clause in &elems[2..] {
+        style = style_clause(style, clause, palette)?;
+    }
+
+    Ok(Span::styled(elems[1].to_string(), style))
+}
+
+fn text_style<'a>(mut text: Text<'a>, exp: &Exp, palette: Option<&Palette>) -> Result<Text<'a>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "style")?;
+
+    for clause in elems.iter().skip(1) {
+        if let Ok(align) = align(clause) {
+            text = text.alignment(align);
+        } else {
+            text.style = style_clause(text.style, clause, palette)?;
+        }
+    }
+
+    Ok(text)
+}
+
+fn align(exp: &Exp) -> Result<Alignment> {
+    let elems = create_list_with_len(exp, 2)?;
+    check_symbol(&elems[0], "align")?;
+
+    create_alignment(&elems[1])
+}
+
+pub fn create_columns_at(exp: &Exp) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 3)?;
+    check_symbol(&elems[0], "columns-at")?;
+
+    let stops = elems[1]
+        .as_list()
+        .ok_or_else(|| RenderTreeError::ExpectedList(ErrorContext::from(&elems[1])))?
+        .iter()
+        .map(create_integer)
+        .collect::<Result<Vec<_>>>()?;
+    let columns = &elems[2..];
+    if columns.len() > stops.len() + 1 {
+        return Err(RenderTreeError::InvalidLength(exp.into()));
+    }
+
+    let mut line = String::new();
+    for (i, column) in columns.iter().enumerate() {
+        if i > 0 {
+            let stop = stops[i - 1].max(0) as usize;
+            let width = Line::raw(line.as_str()).width();
+            let padding = if width > stop { 1 } else { stop - width };
+            line.push_str(&" ".repeat(padding));
+        }
+        line.push_str(&column.to_string());
+    }
+
+    Ok(RenderTree::Text(Text::raw(line)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::renderer::render_tree;
+    use ratatui::{
+        backend::TestBackend,
+        buffer::Buffer,
+        style::{Color, Modifier},
+        Terminal,
+    };
     fn parse(str: &str) -> Exp {
         let mut parser = topogi_lang::parser::Parser::new(str);
         parser.parse_exp().unwrap()
@@ -20,8 +147,135 @@ mod tests {
     fn test_create_text() {
         let exp = parse(r#""hello world""#);
         assert_eq!(
-            create_text(&exp),
+            create_text(&exp, &RenderContext::default()),
+            Ok(RenderTree::Text(Text::raw("hello world")))
+        );
+
+        let exp = parse(r#"(text "hello world")"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
             Ok(RenderTree::Text(Text::raw("hello world")))
         );
     }
+
+    #[test]
+    fn test_multiline_text() {
+        let exp = parse("(text \"line1\nline2\")");
+        let tree = create_text(&exp, &RenderContext::default()).unwrap();
+        assert_eq!(tree, RenderTree::Text(Text::raw("line1\nline2")));
+
+        let mut terminal = Terminal::new(TestBackend::new(5, 2)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .assert_buffer(&Buffer::with_lines(["line1", "line2"]));
+    }
+
+    #[test]
+    fn test_text_blink() {
+        let exp = parse(r#"(text "ALERT" (style (modifier bold)) (blink))"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
+            Ok(RenderTree::Blink {
+                visible: true,
+                content: Box::new(RenderTree::Text(
+                    Text::raw("ALERT").style(Style::default().add_modifier(Modifier::BOLD))
+                )),
+            })
+        );
+
+        let exp = parse(r#"(text "ALERT" (style (modifier blink)))"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
+            Ok(RenderTree::Text(Text::raw("ALERT").style(
+                Style::default().add_modifier(Modifier::SLOW_BLINK)
+            )))
+        );
+    }
+
+    #[test]
+    fn test_text_spans() {
+        let exp = parse(r#"(text (span "ERROR" (fg red)) (span " something happened"))"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
+            Ok(RenderTree::Text(Text::from(Line::from(vec![
+                Span::styled("ERROR", Style::default().fg(Color::Red)),
+                Span::raw(" something happened"),
+            ]))))
+        );
+
+        let exp = parse(r#"(text (span "bad" (fg nocolor)))"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
+            Err(RenderTreeError::ExpectedSymbol(
+                "color",
+                parse("nocolor").into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_text_modifiers() {
+        let exp = parse(r#"(text (span "bold" (modifier bold) (modifier underline)))"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
+            Ok(RenderTree::Text(Text::from(Line::from(Span::styled(
+                "bold",
+                Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            )))))
+        );
+
+        let exp = parse(r#"(text "dim" (style (modifier dim)))"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
+            Ok(RenderTree::Text(
+                Text::raw("dim").style(Style::default().add_modifier(Modifier::DIM))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_text_align() {
+        let exp = parse(r#"(text "centered" (style (align center)))"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
+            Ok(RenderTree::Text(
+                Text::raw("centered").alignment(Alignment::Center)
+            ))
+        );
+
+        let exp = parse(r#"(text "right" (style (align right)))"#);
+        assert_eq!(
+            create_text(&exp, &RenderContext::default()),
+            Ok(RenderTree::Text(
+                Text::raw("right").alignment(Alignment::Right)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_create_columns_at() {
+        let exp = parse(r#"(columns-at (10 20) "name" "type" "value")"#);
+        assert_eq!(
+            create_columns_at(&exp),
+            Ok(RenderTree::Text(Text::raw("name      type      value")))
+        );
+
+        let exp = parse(r#"(columns-at (4) "overlong" "next")"#);
+        assert_eq!(
+            create_columns_at(&exp),
+            Ok(RenderTree::Text(Text::raw("overlong next")))
+        );
+
+        let exp = parse(r#"(columns-at (4) "a" "b" "c")"#);
+        assert_eq!(
+            create_columns_at(&exp),
+            Err(RenderTreeError::InvalidLength(exp.clone().into()))
+        );
+    }
 }
@@ -0,0 +1,105 @@
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, symbols, widgets::Widget};
+use topogi_lang::ast::Exp;
+
+use crate::{
+    render_tree::{
+        check_symbol, create_float, create_list_with_minlen, RenderContext, RenderTree, Result,
+    },
+    style::color_clause,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerticalGauge {
+    pub ratio: f64,
+    pub style: Style,
+}
+
+impl VerticalGauge {
+    pub fn new(ratio: f64) -> Self {
+        VerticalGauge {
+            ratio: ratio.clamp(0.0, 1.0),
+            style: Style::default(),
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl Widget for &VerticalGauge {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        buf.set_style(area, self.style);
+
+        let filled = (self.ratio * f64::from(area.height)).round() as u16;
+        for y in area.bottom().saturating_sub(filled)..area.bottom() {
+            for x in area.left()..area.right() {
+                buf.get_mut(x, y).set_symbol(symbols::block::FULL);
+            }
+        }
+    }
+}
+
+pub fn create_vgauge(exp: &Exp, ctx: &RenderContext) -> Result<RenderTree<'static>> {
+    let elems = create_list_with_minlen(exp, 2)?;
+    check_symbol(&elems[0], "vgauge")?;
+
+    let ratio = create_float(&elems[1])?;
+    let mut style = Style::default();
+    for clause in elems.iter().skip(2) {
+        style = color_clause(style, clause, ctx.palette)?;
+    }
+
+    Ok(RenderTree::VGauge(VerticalGauge::new(ratio).style(style)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::render_tree;
+    use ratatui::{backend::TestBackend, style::Color, Terminal};
+
+    fn parse(str: &str) -> Exp {
+        let mut parser = topogi_lang::parser::Parser::new(str);
+        parser.parse_exp().unwrap()
+    }
+
+    #[test]
+    fn test_create_vgauge() {
+        let exp = parse(r#"(vgauge 0.7 (fg green))"#);
+        assert_eq!(
+            create_vgauge(&exp, &RenderContext::default()),
+            Ok(RenderTree::VGauge(
+                VerticalGauge::new(0.7).style(Style::default().fg(Color::Green))
+            ))
+        );
+
+        let exp = parse(r#"(vgauge 1.5)"#);
+        assert_eq!(
+            create_vgauge(&exp, &RenderContext::default()),
+            Ok(RenderTree::VGauge(VerticalGauge::new(1.0)))
+        );
+    }
+
+    #[test]
+    fn test_render_vgauge() {
+        let tree = RenderTree::VGauge(VerticalGauge::new(0.7));
+        let mut terminal = Terminal::new(TestBackend::new(2, 10)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                render_tree(&tree, frame, area);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let filled = (0..10)
+            .filter(|y| buffer.get(0, *y).symbol() == symbols::block::FULL)
+            .count();
+        assert_eq!(filled, 7);
+        assert_eq!(buffer.get(0, 2).symbol(), " ");
+        assert_eq!(buffer.get(0, 3).symbol(), symbols::block::FULL);
+    }
+}